@@ -161,4 +161,63 @@ pub struct DateTimeEntity {
     pub raw_timestamp_ns: i64,
     /// A regular field to ensure mixing with dates works
     pub label: String,
-}
\ No newline at end of file
+}
+/// Entity with an HNSW-indexed vector, to test nearest neighbor search
+#[derive(Debug)]
+#[entity]
+pub struct City {
+    #[id]
+    pub id: u64,
+    pub name: String,
+    /// latitude, longitude
+    #[hnsw(dimensions = 2, distance = "euclidean")]
+    pub location: Vec<f32>,
+}
+
+/// Entity with all scalar vector types, to test flatten/inflate round-trips
+#[derive(Debug)]
+#[entity]
+pub struct VectorEntity {
+    #[id]
+    pub id: u64,
+    pub t_vec_bool: Vec<bool>,
+    pub t_vec_i16: Vec<i16>,
+    pub t_vec_u16: Vec<u16>,
+    pub t_vec_char: Vec<char>,
+    pub t_vec_i32: Vec<i32>,
+    pub t_vec_u32: Vec<u32>,
+    pub t_vec_i64: Vec<i64>,
+    pub t_vec_u64: Vec<u64>,
+    pub t_vec_f32: Vec<f32>,
+    pub t_vec_f64: Vec<f64>,
+    pub t_vec_date: Vec<DateTime>,
+    pub t_vec_date_nano: Vec<DateTimeNano>,
+    pub t_vec_string: Vec<String>,
+    pub opt_vec_i16: Option<Vec<i16>>,
+    pub opt_vec_i64: Option<Vec<i64>>,
+    pub opt_vec_f64: Option<Vec<f64>>,
+    pub opt_vec_char: Option<Vec<char>>,
+    pub opt_vec_date: Option<Vec<DateTime>>,
+}
+
+/// Time series entity, the timestamp accompanies the id
+#[derive(Debug)]
+#[entity]
+pub struct SensorReading {
+    #[id]
+    pub id: u64,
+    #[id_companion]
+    pub measured_at: DateTime,
+    pub value: f64,
+}
+
+/// Cache entry that ages out, see Store::remove_expired
+#[derive(Debug)]
+#[entity]
+pub struct CacheEntry {
+    #[id]
+    pub id: u64,
+    pub key: String,
+    #[expiration_time]
+    pub expires_at: DateTime,
+}
@@ -0,0 +1,50 @@
+#![cfg(feature = "admin")]
+
+use example::{make_factory_map, make_model};
+use objectbox::admin::Admin;
+use objectbox::{error, opt::Opt, store::Store};
+use std::net::TcpStream;
+
+use serial_test::serial;
+
+fn setup_store() -> error::Result<Store> {
+    let mut model = make_model();
+    let opt = Opt::from_model(&mut model)?;
+    let trait_map = make_factory_map();
+    Store::new(opt, trait_map)
+}
+
+#[test]
+#[serial]
+fn test_admin_start_and_close() -> error::Result<()> {
+    let store = setup_store()?;
+
+    let r = Admin::builder()
+        .store(&store)
+        .bind("127.0.0.1:0")
+        .threads(1)
+        .start();
+
+    if !Admin::is_available() {
+        let err = r.err().expect("admin should not start");
+        assert_eq!(error::ErrorKind::FeatureNotAvailable, err.kind());
+        return Ok(());
+    }
+
+    let admin = r?;
+    let port = admin.port();
+    assert_ne!(0, port);
+    assert!(TcpStream::connect(("127.0.0.1", port)).is_ok());
+
+    drop(admin);
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_admin_requires_store() {
+    if !Admin::is_available() {
+        return;
+    }
+    assert!(Admin::builder().bind("127.0.0.1:0").start().is_err());
+}
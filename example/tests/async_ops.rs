@@ -0,0 +1,77 @@
+use example::{make_factory_map, make_model, Entity3};
+use objectbox::{error, opt::Opt, store::Store};
+
+use serial_test::serial;
+
+fn setup_store() -> error::Result<Store> {
+    let mut model = make_model();
+    let opt = Opt::from_model(&mut model)?;
+    let trait_map = make_factory_map();
+    Store::new(opt, trait_map)
+}
+
+#[test]
+#[serial]
+fn test_async_put_update_remove() -> error::Result<()> {
+    let store = setup_store()?;
+    let mut box3 = store.get_box::<Entity3>()?;
+    box3.remove_all()?;
+
+    let mut async3 = box3.async_box()?;
+
+    let mut e1 = Entity3 {
+        id: 0,
+        hello: "first".to_string(),
+    };
+    let mut e2 = Entity3 {
+        id: 0,
+        hello: "second".to_string(),
+    };
+    let id1 = async3.put(&mut e1)?;
+    let id2 = async3.insert(&mut e2)?;
+    assert_ne!(0, id1);
+    assert_eq!(id1, e1.id, "ids are assigned when enqueued");
+    assert_eq!(id2, e2.id);
+
+    assert!(store.await_async_completion());
+    assert_eq!(2, store.get_box::<Entity3>()?.count()?);
+
+    e1.hello = "updated".to_string();
+    async3.update(&mut e1)?;
+    assert!(store.await_async_completion());
+    assert_eq!("updated", box3.get(id1)?.unwrap().hello);
+
+    async3.remove(id2)?;
+    assert!(store.await_async_completion());
+    assert_eq!(1, store.get_box::<Entity3>()?.count()?);
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_async_put_object() -> error::Result<()> {
+    let store = setup_store()?;
+    let mut box3 = store.get_box::<Entity3>()?;
+    box3.remove_all()?;
+
+    let mut async3 = box3.async_box_with_timeout(1000)?;
+    let mut e = Entity3 {
+        id: 0,
+        hello: "object".to_string(),
+    };
+    let id = async3.put_object(&mut e)?;
+    assert_eq!(id, e.id);
+
+    assert!(store.await_async_completion());
+    assert_eq!("object", box3.get(id)?.unwrap().hello);
+
+    // update without an id is rejected right away
+    let mut no_id = Entity3 {
+        id: 0,
+        hello: "no id".to_string(),
+    };
+    assert!(async3.update(&mut no_id).is_err());
+
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use example::{make_factory_map, make_model, Entity3};
+use objectbox::backup::{BackupFlags, BackupRestoreFlags};
+use objectbox::{c, error, opt::Opt, store::Store};
+use std::fs;
+
+use serial_test::serial;
+
+fn setup_store() -> error::Result<Store> {
+    let mut model = make_model();
+    let opt = Opt::from_model(&mut model)?;
+    let trait_map = make_factory_map();
+    Store::new(opt, trait_map)
+}
+
+#[test]
+#[serial]
+fn test_backup_and_restore() -> error::Result<()> {
+    let dir = std::env::temp_dir().join("objectbox-backup-test");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).expect("temp dir");
+    let backup_file = dir.join("store.backup");
+
+    let store = setup_store()?;
+    let mut box3 = store.get_box::<Entity3>()?;
+    box3.remove_all()?;
+    for s in ["one", "two", "three"] {
+        box3.put(&mut Entity3 {
+            id: 0,
+            hello: s.to_string(),
+        })?;
+    }
+
+    // backup is a server-only feature of the native library
+    if !unsafe { c::obx_has_feature(c::OBXFeature_Backup) } {
+        assert!(store.backup_to(&backup_file, BackupFlags::NONE).is_err());
+        return Ok(());
+    }
+
+    store.backup_to(
+        &backup_file,
+        BackupFlags::EXCLUDE_TIMESTAMP | BackupFlags::EXCLUDE_SALT,
+    )?;
+    assert!(backup_file.exists());
+
+    let mut model = make_model();
+    let opt = Opt::from_model(&mut model)?;
+    opt.directory(&dir.join("restored"))?
+        .restore_from_backup(&backup_file, BackupRestoreFlags::NONE)?;
+    let restored = Store::new(opt, make_factory_map())?;
+    let hellos = restored
+        .get_box::<Entity3>()?
+        .get_all()?
+        .into_iter()
+        .map(|e| e.hello)
+        .collect::<Vec<_>>();
+    assert_eq!(vec!["one", "two", "three"], hellos);
+
+    drop(restored);
+    let _ = fs::remove_dir_all(&dir);
+    Ok(())
+}
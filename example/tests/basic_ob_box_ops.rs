@@ -1,8 +1,9 @@
 use example::{make_factory_map, make_model, Entity, Entity2, Entity3};
 use objectbox::error;
 use objectbox::traits::{self, IdExt};
+use objectbox::r#box::PutMode;
 use objectbox::{opt::Opt, store::Store};
-use std::rc;
+use std::sync;
 
 use serial_test::serial;
 
@@ -23,15 +24,15 @@ fn test_box_put_and_count_and_remove_all() -> error::Result<()> {
 
     let trait_map2 = make_factory_map();
     let f1 = trait_map2
-        .get::<rc::Rc<dyn traits::EntityFactoryExt<Entity>>>()
+        .get::<sync::Arc<dyn traits::EntityFactoryExt<Entity>>>()
         .unwrap()
         .clone();
     let f2 = trait_map2
-        .get::<rc::Rc<dyn traits::EntityFactoryExt<Entity2>>>()
+        .get::<sync::Arc<dyn traits::EntityFactoryExt<Entity2>>>()
         .unwrap()
         .clone();
     let f3 = trait_map2
-        .get::<rc::Rc<dyn traits::EntityFactoryExt<Entity3>>>()
+        .get::<sync::Arc<dyn traits::EntityFactoryExt<Entity3>>>()
         .unwrap()
         .clone();
 
@@ -113,20 +114,103 @@ fn test_box_put_and_count_and_remove_all() -> error::Result<()> {
         ])?;
 
         assert!(box1.contains_many(&ids)?.iter().all(|b| *b));
+        assert!(box1.contains_all(&ids)?);
 
         ids.push(404);
 
         assert!(box1.contains_many(&ids)?.iter().any(|b| !*b));
+        assert!(!box1.contains_many(&ids)?[5]);
+        assert!(!box1.contains_all(&ids)?);
 
         assert_ne!(true, box1.contains(404)?);
 
         assert_ne!(true, box1.remove_with_id(404)?);
 
-        // remove_many uses remove_with_id, so its transitively tested
-        assert_ne!(true, box1.remove_many(&ids)?[5]);
+        // only the missing id reports false
+        let removed = box1.remove_many(&ids[1..])?;
+        assert!(removed[..4].iter().all(|b| *b));
+        assert!(!removed[4]);
+
+        assert_eq!(vec![true, false, false, false, false, false], box1.remove_many(&ids)?);
 
         assert!(box1.is_empty()?);
     }
 
     Ok(())
 }
+
+#[test]
+#[serial]
+fn test_box_put_modes() -> error::Result<()> {
+    let mut model = make_model();
+    let opt = Opt::from_model(&mut model)?;
+    let trait_map = make_factory_map();
+    let store = Store::new(opt, trait_map)?;
+
+    let mut box3 = store.get_box::<Entity3>()?;
+    box3.remove_all()?;
+
+    let mut e = Entity3 {
+        id: 0,
+        hello: "insert".to_string(),
+    };
+
+    // update requires an existing id
+    let err = box3.update(&mut e).unwrap_err();
+    assert_eq!(error::ErrorKind::IdNotFound, err.kind());
+
+    let id = box3.insert(&mut e)?;
+    assert_eq!(id, e.id);
+
+    // inserting the same id twice fails
+    let err = box3.insert(&mut e).unwrap_err();
+    assert_eq!(error::ErrorKind::IdAlreadyExists, err.kind());
+
+    e.hello = "update".to_string();
+    box3.put_with_mode(&mut e, PutMode::Update)?;
+    assert_eq!("update", box3.get(id)?.unwrap().hello);
+
+    // an unknown id can't be updated
+    let mut unknown = Entity3 {
+        id: id + 1000,
+        hello: "unknown".to_string(),
+    };
+    let err = box3.update(&mut unknown).unwrap_err();
+    assert_eq!(error::ErrorKind::IdNotFound, err.kind());
+
+    // *_many are all or nothing
+    let mut a = Entity3 {
+        id: 0,
+        hello: "a".to_string(),
+    };
+    assert!(box3.update_many(vec![&mut e, &mut a]).is_err());
+    assert_eq!(1, box3.count()?);
+
+    let ids = box3.insert_many(vec![&mut a])?;
+    assert_eq!(vec![a.id], ids);
+    assert_eq!(2, box3.count()?);
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_box_many_with_empty_input() -> error::Result<()> {
+    let mut model = make_model();
+    let opt = Opt::from_model(&mut model)?;
+    let trait_map = make_factory_map();
+    let store = Store::new(opt, trait_map)?;
+
+    let mut box3 = store.get_box::<Entity3>()?;
+    box3.remove_all()?;
+
+    assert!(box3.put_many(vec![])?.is_empty());
+    assert!(box3.update_many(vec![])?.is_empty());
+    assert!(box3.get_many(&[])?.is_empty());
+    assert!(box3.contains_all(&[])?);
+    assert!(box3.remove_many(&[])?.is_empty());
+    assert!(box3.contains_many(&[])?.is_empty());
+    assert_eq!(0, box3.count()?);
+
+    Ok(())
+}
@@ -1,7 +1,7 @@
 use example::{
     make_factory_map, make_model, new_entity_condition_factory, Entity, EntityConditionFactory,
 };
-use objectbox::{error, opt::Opt, store::Store};
+use objectbox::{error, opt::Opt, query::order::OrderFlags, store::Store};
 
 use serial_test::serial;
 
@@ -138,3 +138,271 @@ fn basic_query_tests() -> error::Result<()> {
 
     Ok(())
 }
+
+fn new_entity(unique_i32: i32, t_i64: i64, t_f64: f64, t_string: Option<&str>) -> Entity {
+    Entity {
+        id: 0,
+        index_u32: 1,
+        t_bool: false,
+        t_u8: 2,
+        t_i8: 3,
+        t_i16: 4,
+        t_u16: 5,
+        unique_i32,
+        t_i32: 7,
+        t_u32: 8,
+        t_u64: 9,
+        t_i64,
+        t_f32: 12.0,
+        t_f64,
+        t_string: t_string.map(|s| s.to_string()),
+        t_char: 'c',
+        t_vec_string: vec![],
+        t_vec_bytes: vec![],
+    }
+}
+
+#[test]
+#[serial]
+fn property_query_tests() -> error::Result<()> {
+    let mut model = make_model();
+    let opt = Opt::from_model(&mut model)?;
+    let trait_map = make_factory_map();
+    let store = Store::new(opt, trait_map)?;
+
+    let mut box1 = store.get_box::<Entity>()?;
+    box1.remove_all()?;
+
+    let EntityConditionFactory {
+        t_i64,
+        t_f64,
+        t_string,
+        ..
+    } = new_entity_condition_factory();
+
+    box1.put(&mut new_entity(1, 10, 1.5, Some("a")))?;
+    box1.put(&mut new_entity(2, 20, 2.5, Some("A")))?;
+    box1.put(&mut new_entity(3, 30, 3.5, Some("b")))?;
+    box1.put(&mut new_entity(4, 30, 4.5, None))?;
+
+    let query = box1.query(&mut t_i64.gt(0))?;
+
+    let pq = query.property(t_i64.as_ref())?;
+    assert_eq!(4, pq.count()?);
+    assert_eq!(Some(10), pq.min_int()?);
+    assert_eq!(Some(30), pq.max_int()?);
+    assert_eq!(90, pq.sum_int()?);
+    assert_eq!(vec![10, 20, 30, 30], pq.find_int64s(None)?);
+    pq.distinct(true)?;
+    assert_eq!(3, pq.count()?);
+
+    let pq = query.property(t_f64.as_ref())?;
+    assert_eq!(Some(1.5), pq.min()?);
+    assert_eq!(Some(4.5), pq.max()?);
+    assert_eq!(12.0, pq.sum()?);
+    assert_eq!(Some(3.0), pq.avg()?);
+    assert_eq!(4, pq.find_doubles(None)?.len());
+
+    let pq = query.property(t_string.as_ref())?;
+    assert_eq!(3, pq.find_strings(None)?.len());
+    assert_eq!(4, pq.find_strings(Some("null"))?.len());
+    pq.distinct_case(true, false)?;
+    assert_eq!(2, pq.count()?);
+    pq.distinct_case(true, true)?;
+    assert_eq!(3, pq.count()?);
+
+    let empty = box1.query(&mut t_i64.gt(100))?;
+    let pq = empty.property(t_i64.as_ref())?;
+    assert_eq!(None, pq.min_int()?);
+    assert_eq!(0, pq.sum_int()?);
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn query_param_tests() -> error::Result<()> {
+    let mut model = make_model();
+    let opt = Opt::from_model(&mut model)?;
+    let trait_map = make_factory_map();
+    let store = Store::new(opt, trait_map)?;
+
+    let mut box1 = store.get_box::<Entity>()?;
+    box1.remove_all()?;
+
+    let EntityConditionFactory {
+        t_i64,
+        t_f64,
+        t_string,
+        ..
+    } = new_entity_condition_factory();
+
+    box1.put(&mut new_entity(1, 10, 1.5, Some("a")))?;
+    box1.put(&mut new_entity(2, 20, 2.5, Some("b")))?;
+    box1.put(&mut new_entity(3, 30, 3.5, Some("c")))?;
+
+    // by property
+    let query = box1.query(&mut t_i64.gt(0))?;
+    assert_eq!(3, query.count()?);
+    query.set_param_int(t_i64.as_ref(), 15)?;
+    assert_eq!(2, query.count()?);
+
+    let query = box1.query(&mut t_f64.between(0.0, 10.0))?;
+    assert_eq!(3, query.count()?);
+    query.set_param_doubles(t_f64.as_ref(), 2.0, 3.0)?;
+    assert_eq!(1, query.count()?);
+
+    // by alias
+    let query =
+        box1.query(&mut (t_i64.gt(0).alias("min") & t_string.eq("a".to_string()).alias("s")))?;
+    assert_eq!(1, query.count()?);
+    query.set_param_alias_string("s", "c")?;
+    assert_eq!(1, query.count()?);
+    query.set_param_alias_int("min", 30)?;
+    assert_eq!(0, query.count()?);
+
+    let query = box1.query(&mut t_i64.member_of(vec![10]).alias("ids"))?;
+    assert_eq!(1, query.count()?);
+    query.set_param_alias_int64s("ids", &[10, 20, 30])?;
+    assert_eq!(3, query.count()?);
+
+    assert!(query.set_param_alias_int("unknown", 1).is_err());
+
+    // an invalid alias fails the query instead of being dropped
+    assert!(box1.query(&mut t_i64.gt(0).alias("bad\0alias")).is_err());
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn query_order_tests() -> error::Result<()> {
+    let mut model = make_model();
+    let opt = Opt::from_model(&mut model)?;
+    let trait_map = make_factory_map();
+    let store = Store::new(opt, trait_map)?;
+
+    let mut box1 = store.get_box::<Entity>()?;
+    box1.remove_all()?;
+
+    let EntityConditionFactory {
+        t_i64,
+        t_f64,
+        t_string,
+        ..
+    } = new_entity_condition_factory();
+
+    box1.put(&mut new_entity(1, 20, 2.0, Some("b")))?;
+    box1.put(&mut new_entity(2, 10, 3.0, Some("B")))?;
+    box1.put(&mut new_entity(3, 30, 1.0, None))?;
+    box1.put(&mut new_entity(4, 10, 4.0, Some("a")))?;
+
+    let i64s = |v: &[Entity]| v.iter().map(|e| e.t_i64).collect::<Vec<i64>>();
+    let f64s = |v: &[Entity]| v.iter().map(|e| e.t_f64).collect::<Vec<f64>>();
+    let strings = |v: &[Entity]| {
+        v.iter()
+            .map(|e| e.t_string.clone())
+            .collect::<Vec<Option<String>>>()
+    };
+
+    // numeric
+    let query = box1
+        .query_builder(&mut t_i64.gt(0))?
+        .order_by(t_f64.as_ref(), OrderFlags::NONE)?
+        .build()?;
+    assert_eq!(vec![1.0, 2.0, 3.0, 4.0], f64s(&query.find()?));
+
+    let query = box1
+        .query_builder(&mut t_i64.gt(0))?
+        .order_by(t_f64.as_ref(), OrderFlags::DESCENDING)?
+        .build()?;
+    assert_eq!(vec![4.0, 3.0, 2.0, 1.0], f64s(&query.find()?));
+
+    // several sort keys, ties on t_i64 are broken by t_f64
+    let query = box1
+        .query_builder(&mut t_i64.gt(0))?
+        .order_by(t_i64.as_ref(), OrderFlags::NONE)?
+        .order_by(t_f64.as_ref(), OrderFlags::DESCENDING)?
+        .build()?;
+    let found = query.find()?;
+    assert_eq!(vec![4.0, 3.0, 2.0, 1.0], f64s(&found));
+    assert_eq!(vec![10, 10, 20, 30], i64s(&found));
+
+    // strings, nulls first by default
+    let query = box1
+        .query_builder(&mut t_i64.gt(0))?
+        .order_by(t_string.as_ref(), OrderFlags::CASE_SENSITIVE)?
+        .build()?;
+    assert_eq!(
+        vec![
+            None,
+            Some("B".to_string()),
+            Some("a".to_string()),
+            Some("b".to_string())
+        ],
+        strings(&query.find()?)
+    );
+
+    // optional, nulls last
+    let query = box1
+        .query_builder(&mut t_i64.gt(0))?
+        .order_by(
+            t_string.as_ref(),
+            OrderFlags::DESCENDING | OrderFlags::CASE_SENSITIVE | OrderFlags::NULLS_LAST,
+        )?
+        .build()?;
+    assert_eq!(
+        vec![
+            Some("b".to_string()),
+            Some("a".to_string()),
+            Some("B".to_string()),
+            None
+        ],
+        strings(&query.find()?)
+    );
+
+    // stable across runs
+    let first = i64s(&query.find()?);
+    assert_eq!(first, i64s(&query.find()?));
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn query_find_first_unique_tests() -> error::Result<()> {
+    let mut model = make_model();
+    let opt = Opt::from_model(&mut model)?;
+    let trait_map = make_factory_map();
+    let store = Store::new(opt, trait_map)?;
+
+    let mut box1 = store.get_box::<Entity>()?;
+    box1.remove_all()?;
+
+    let EntityConditionFactory { t_i64, t_f64, .. } = new_entity_condition_factory();
+
+    box1.put(&mut new_entity(1, 10, 2.0, Some("b")))?;
+    box1.put(&mut new_entity(2, 10, 1.0, Some("a")))?;
+    box1.put(&mut new_entity(3, 30, 3.0, None))?;
+
+    // no match
+    let query = box1.query(&mut t_i64.eq(99))?;
+    assert!(query.find_first()?.is_none());
+    assert!(query.find_unique()?.is_none());
+
+    // one match
+    let query = box1.query(&mut t_i64.eq(30))?;
+    assert_eq!(3.0, query.find_first()?.expect("first").t_f64);
+    assert_eq!(3.0, query.find_unique()?.expect("unique").t_f64);
+
+    // several matches, first respects the order
+    let query = box1
+        .query_builder(&mut t_i64.eq(10))?
+        .order_by(t_f64.as_ref(), OrderFlags::NONE)?
+        .build()?;
+    assert_eq!(1.0, query.find_first()?.expect("first").t_f64);
+    let err = query.find_unique().expect_err("not unique");
+    assert_eq!(error::ErrorKind::NonUniqueResult, err.kind());
+
+    Ok(())
+}
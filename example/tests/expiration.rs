@@ -0,0 +1,65 @@
+use example::{make_factory_map, make_model, CacheEntry};
+use objectbox::datetime::DateTime;
+use objectbox::{error, opt::Opt, store::Store};
+use std::sync::mpsc;
+use std::time::Duration;
+
+use serial_test::serial;
+
+fn setup_store() -> error::Result<Store> {
+    let mut model = make_model();
+    let opt = Opt::from_model(&mut model)?;
+    let trait_map = make_factory_map();
+    Store::new(opt, trait_map)
+}
+
+fn put_entries(store: &Store) -> error::Result<()> {
+    let mut box_ = store.get_box::<CacheEntry>()?;
+    box_.remove_all()?;
+    let now = DateTime::now().to_millis();
+    for (key, expires_at) in [
+        ("stale 1", now - 60_000),
+        ("stale 2", now - 1),
+        ("fresh", now + 3_600_000),
+    ] {
+        box_.put(&mut CacheEntry {
+            id: 0,
+            key: key.to_string(),
+            expires_at: DateTime::from_millis(expires_at),
+        })?;
+    }
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_remove_expired() -> error::Result<()> {
+    let store = setup_store()?;
+    put_entries(&store)?;
+
+    assert_eq!(2, store.remove_expired::<CacheEntry>()?);
+    let left = store.get_box::<CacheEntry>()?.get_all()?;
+    assert_eq!(1, left.len());
+    assert_eq!("fresh", left[0].key);
+
+    assert_eq!(0, store.remove_expired::<CacheEntry>()?);
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_remove_expired_async() -> error::Result<()> {
+    let store = setup_store()?;
+    put_entries(&store)?;
+
+    let (sender, receiver) = mpsc::channel();
+    store.remove_expired_async::<CacheEntry>(move |r| {
+        let _ = sender.send(r.is_ok());
+    })?;
+    assert_eq!(Ok(true), receiver.recv_timeout(Duration::from_secs(5)));
+
+    assert_eq!(1, store.get_box::<CacheEntry>()?.count()?);
+
+    Ok(())
+}
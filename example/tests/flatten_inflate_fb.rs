@@ -1,4 +1,4 @@
-use std::rc;
+use std::sync;
 
 use example::make_factory_map;
 use objectbox::flatbuffers::{FlatBufferBuilder, Table};
@@ -10,15 +10,15 @@ use objectbox::traits::FBOBBridge;
 fn test_write_and_read_fb() {
     let trait_map2 = make_factory_map();
     let f1 = trait_map2
-        .get::<rc::Rc<dyn traits::EntityFactoryExt<example::Entity>>>()
+        .get::<sync::Arc<dyn traits::EntityFactoryExt<example::Entity>>>()
         .unwrap()
         .clone();
     let f2 = trait_map2
-        .get::<rc::Rc<dyn traits::EntityFactoryExt<example::Entity2>>>()
+        .get::<sync::Arc<dyn traits::EntityFactoryExt<example::Entity2>>>()
         .unwrap()
         .clone();
     let f3 = trait_map2
-        .get::<rc::Rc<dyn traits::EntityFactoryExt<example::Entity3>>>()
+        .get::<sync::Arc<dyn traits::EntityFactoryExt<example::Entity3>>>()
         .unwrap()
         .clone();
 
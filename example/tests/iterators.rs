@@ -0,0 +1,152 @@
+use example::{
+    make_factory_map, make_model, new_entity3_condition_factory, Entity3,
+    Entity3ConditionFactory,
+};
+use objectbox::{error, opt::Opt, store::Store};
+
+use serial_test::serial;
+
+fn setup_store() -> error::Result<Store> {
+    let mut model = make_model();
+    let opt = Opt::from_model(&mut model)?;
+    let trait_map = make_factory_map();
+    Store::new(opt, trait_map)
+}
+
+#[test]
+#[serial]
+fn test_box_iter() -> error::Result<()> {
+    let store = setup_store()?;
+    let mut box3 = store.get_box::<Entity3>()?;
+    box3.remove_all()?;
+
+    assert_eq!(0, box3.iter()?.count());
+
+    for i in 0..10 {
+        box3.put(&mut Entity3 {
+            id: 0,
+            hello: format!("hello {i}"),
+        })?;
+    }
+
+    let mut count = 0;
+    for r in box3.iter()? {
+        let e = r?;
+        assert_eq!(format!("hello {count}"), e.hello);
+        count += 1;
+    }
+    assert_eq!(10, count);
+
+    // stop early, the read transaction is closed on drop
+    let first_two = box3.iter()?.take(2).collect::<error::Result<Vec<Entity3>>>()?;
+    assert_eq!(2, first_two.len());
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_query_iter() -> error::Result<()> {
+    let store = setup_store()?;
+    let mut box3 = store.get_box::<Entity3>()?;
+    box3.remove_all()?;
+
+    for s in ["world", "real world", "REAL world", "other"] {
+        box3.put(&mut Entity3 {
+            id: 0,
+            hello: s.to_string(),
+        })?;
+    }
+
+    let Entity3ConditionFactory { hello, .. } = new_entity3_condition_factory();
+
+    let query = box3.query(&mut hello.contains("world"))?;
+    let found = query.iter()?.collect::<error::Result<Vec<Entity3>>>()?;
+    assert_eq!(3, found.len());
+    assert!(found.iter().all(|e| e.hello.to_lowercase().contains("world")));
+
+    let query = box3.query(&mut hello.contains("xyz"))?;
+    assert_eq!(0, query.iter()?.count());
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_query_for_each() -> error::Result<()> {
+    let store = setup_store()?;
+    let mut box3 = store.get_box::<Entity3>()?;
+    box3.remove_all()?;
+
+    for s in ["world", "real world", "other"] {
+        box3.put(&mut Entity3 {
+            id: 0,
+            hello: s.to_string(),
+        })?;
+    }
+
+    let Entity3ConditionFactory { hello, .. } = new_entity3_condition_factory();
+
+    let query = box3.query(&mut hello.contains("world"))?;
+    let mut visited = Vec::new();
+    query.for_each(|e| visited.push(e.hello))?;
+    visited.sort();
+    assert_eq!(vec!["real world", "world"], visited);
+
+    // a panic in the closure reaches the caller
+    let r = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        query.for_each(|_| panic!("stop"))
+    }));
+    assert!(r.is_err());
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_query_update_each() -> error::Result<()> {
+    let store = setup_store()?;
+    let mut box3 = store.get_box::<Entity3>()?;
+    box3.remove_all()?;
+
+    for s in [
+        "active 1",
+        "active 2",
+        "expired 1",
+        "expired 2",
+        "expired 3",
+    ] {
+        box3.put(&mut Entity3 {
+            id: 0,
+            hello: s.to_string(),
+        })?;
+    }
+
+    let Entity3ConditionFactory { hello, .. } = new_entity3_condition_factory();
+
+    // only the objects the closure returns true for are written
+    let query = box3.query(&mut hello.starts_with("expired"))?;
+    let updated = query.update_each(|e| {
+        if e.hello.ends_with('3') {
+            return false;
+        }
+        e.hello = e.hello.replace("expired", "inactive");
+        true
+    })?;
+    assert_eq!(2, updated);
+
+    let query = box3.query(&mut hello.starts_with("inactive"))?;
+    assert_eq!(2, query.count()?);
+    let query = box3.query(&mut hello.starts_with("expired"))?;
+    assert_eq!(
+        vec!["expired 3".to_string()],
+        query
+            .find()?
+            .into_iter()
+            .map(|e| e.hello)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(5, box3.count()?);
+
+    Ok(())
+}
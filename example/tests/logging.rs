@@ -0,0 +1,29 @@
+use example::{make_factory_map, make_model, Entity3};
+use objectbox::logging::LogLevel;
+use objectbox::{error, opt::Opt, store::Store};
+use std::sync::Mutex;
+
+use serial_test::serial;
+
+static EVENTS: Mutex<Vec<(LogLevel, String)>> = Mutex::new(Vec::new());
+
+fn record(level: LogLevel, message: &str) {
+    EVENTS.lock().unwrap().push((level, message.to_string()));
+}
+
+#[test]
+#[serial]
+fn test_store_opens_with_log_callback() -> error::Result<()> {
+    let mut model = make_model();
+    let opt = Opt::from_model(&mut model)?;
+    opt.log_callback(record);
+    let store = Store::new(opt, make_factory_map())?;
+    store.get_box::<Entity3>()?.count()?;
+    drop(store);
+
+    // only a selection of events is reported, there may be none
+    for (_, message) in EVENTS.lock().unwrap().iter() {
+        assert!(!message.contains('\0'));
+    }
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use example::{make_factory_map, make_model, Entity2, Entity3};
+use objectbox::{error, opt::Opt, store::Store};
+use std::sync::mpsc;
+use std::time::Duration;
+
+use serial_test::serial;
+
+fn setup_store() -> error::Result<Store> {
+    let mut model = make_model();
+    let opt = Opt::from_model(&mut model)?;
+    let trait_map = make_factory_map();
+    Store::new(opt, trait_map)
+}
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+#[test]
+#[serial]
+fn test_observe_single_type() -> error::Result<()> {
+    let store = setup_store()?;
+    let mut box3 = store.get_box::<Entity3>()?;
+    let mut box2 = store.get_box::<Entity2>()?;
+
+    let (sender, receiver) = mpsc::channel();
+    let observer = store.observe::<Entity3>(move || {
+        let _ = sender.send(());
+    })?;
+
+    box3.put(&mut Entity3 {
+        id: 0,
+        hello: "observed".to_string(),
+    })?;
+    assert!(receiver.recv_timeout(TIMEOUT).is_ok());
+
+    // other types don't trigger this observer
+    box2.put(&mut Entity2 { id: 0, index_u64: 1 })?;
+    assert!(receiver.recv_timeout(Duration::from_millis(200)).is_err());
+
+    drop(observer);
+    box3.put(&mut Entity3 {
+        id: 0,
+        hello: "not observed".to_string(),
+    })?;
+    assert!(receiver.recv_timeout(Duration::from_millis(200)).is_err());
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_observe_channel() -> error::Result<()> {
+    let store = setup_store()?;
+    let mut box3 = store.get_box::<Entity3>()?;
+    let entity3_id = store.entity_id_of::<Entity3>()?;
+
+    let (_observer, receiver) = store.observe_channel()?;
+
+    box3.put(&mut Entity3 {
+        id: 0,
+        hello: "observed".to_string(),
+    })?;
+
+    let type_ids = receiver.recv_timeout(TIMEOUT).expect("no change received");
+    assert!(type_ids.contains(&entity3_id));
+
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use example::{make_factory_map, make_model, Entity3};
+use objectbox::error;
+use objectbox::opt::{OptBuilder, ValidateOnOpenKvFlags, ValidateOnOpenPagesFlags};
+use std::path::Path;
+
+use serial_test::serial;
+
+#[test]
+#[serial]
+fn test_opt_builder_opens_store() -> error::Result<()> {
+    let mut model = make_model();
+    let store = OptBuilder::from_model(&mut model)
+        .validate_on_open_pages(1, ValidateOnOpenPagesFlags::VISIT_LEAF_PAGES)
+        .validate_on_open_kv(ValidateOnOpenKvFlags::NONE)
+        .wal_max_file_size_in_kb(16 * 1024)
+        .wal_max_file_size_on_open_in_kb(4 * 1024)
+        .open(make_factory_map())?;
+
+    let mut box3 = store.get_box::<Entity3>()?;
+    box3.remove_all()?;
+    box3.put(&mut Entity3 {
+        id: 0,
+        hello: "built".to_string(),
+    })?;
+    assert_eq!(1, box3.count()?);
+    Ok(())
+}
+
+#[test]
+fn test_opt_builder_keeps_first_error() {
+    let mut model = make_model();
+    let r = OptBuilder::from_model(&mut model)
+        .directory(Path::new("invalid\0dir"))
+        .max_readers(8)
+        .build();
+    assert!(r.is_err());
+}
@@ -0,0 +1,176 @@
+use example::{
+    make_factory_map, make_model, new_customer_condition_factory, new_order_condition_factory,
+    new_student_condition_factory, new_teacher_condition_factory, Customer,
+    CustomerConditionFactory, Order, OrderConditionFactory, Student, StudentConditionFactory,
+    Teacher, TeacherConditionFactory,
+};
+use objectbox::relations::{ToMany, ToOne};
+use objectbox::{error, opt::Opt, store::Store};
+
+use serial_test::serial;
+
+fn setup_store() -> error::Result<Store> {
+    let mut model = make_model();
+    let opt = Opt::from_model(&mut model)?;
+    let trait_map = make_factory_map();
+    Store::new(opt, trait_map)
+}
+
+fn new_customer(name: &str, email: &str) -> Customer {
+    Customer {
+        id: 0,
+        name: name.to_string(),
+        email: email.to_string(),
+    }
+}
+
+fn new_order(description: &str, amount: f64, customer_id: u64) -> Order {
+    Order {
+        id: 0,
+        description: description.to_string(),
+        amount,
+        customer: ToOne::with_id(customer_id),
+    }
+}
+
+#[test]
+#[serial]
+fn test_to_one_link_query() -> error::Result<()> {
+    let store = setup_store()?;
+    let mut customer_box = store.get_box::<Customer>()?;
+    let mut order_box = store.get_box::<Order>()?;
+    customer_box.remove_all()?;
+    order_box.remove_all()?;
+
+    let acme = customer_box.put(&mut new_customer("Wile", "wile@acme.com"))?;
+    let other = customer_box.put(&mut new_customer("Road", "road@runner.org"))?;
+    order_box.put(&mut new_order("rocket", 100.0, acme))?;
+    order_box.put(&mut new_order("magnet", 25.0, acme))?;
+    order_box.put(&mut new_order("seeds", 5.0, other))?;
+
+    let OrderConditionFactory {
+        customer, amount, ..
+    } = new_order_condition_factory();
+    let CustomerConditionFactory { email, name, .. } = new_customer_condition_factory();
+
+    // orders whose customer's email ends with @acme.com
+    let query = order_box.query(&mut customer.with(email.ends_with("@acme.com")))?;
+    let found = query.find()?;
+    assert_eq!(2, found.len());
+    assert!(found.iter().all(|o| o.customer.get_target_id() == acme));
+
+    // combined with a condition on the order itself
+    let mut c = amount.gt(50.0) & customer.with(email.ends_with("@acme.com"));
+    let found = order_box.query(&mut c)?.find()?;
+    assert_eq!(1, found.len());
+    assert_eq!("rocket", found[0].description);
+
+    // customers with an order cheaper than 10
+    let mut c = customer.backlink().with(amount.lt(10.0));
+    let found = customer_box.query(&mut c)?.find()?;
+    assert_eq!(1, found.len());
+    assert_eq!(other, found[0].id);
+
+    let mut c = customer.with(name.eq("Nobody".to_string()));
+    assert_eq!(0, order_box.query(&mut c)?.count()?);
+
+    // a link can't be one side of an or
+    let mut c = amount.gt(50.0) | customer.with(email.ends_with("@acme.com"));
+    assert!(order_box.query(&mut c).is_err());
+    let mut c = amount.lt(1.0) | (amount.gt(50.0) & customer.with(name.eq("Wile".to_string())));
+    assert!(order_box.query(&mut c).is_err());
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_relation_count_query() -> error::Result<()> {
+    let store = setup_store()?;
+    let mut customer_box = store.get_box::<Customer>()?;
+    let mut order_box = store.get_box::<Order>()?;
+    customer_box.remove_all()?;
+    order_box.remove_all()?;
+
+    let busy = customer_box.put(&mut new_customer("Busy", "busy@acme.com"))?;
+    let single = customer_box.put(&mut new_customer("Single", "single@acme.com"))?;
+    let idle = customer_box.put(&mut new_customer("Idle", "idle@acme.com"))?;
+    for i in 0..3 {
+        order_box.put(&mut new_order(&format!("order {i}"), 1.0, busy))?;
+    }
+    order_box.put(&mut new_order("only", 1.0, single))?;
+
+    let OrderConditionFactory { customer, .. } = new_order_condition_factory();
+    let orders = customer.backlink();
+
+    // customers with 3 orders
+    let mut c = orders.relation_count(3);
+    assert_eq!(vec![busy], customer_box.query(&mut c)?.find_ids()?);
+
+    // customers without orders
+    let mut c = orders.relation_count(0);
+    assert_eq!(vec![idle], customer_box.query(&mut c)?.find_ids()?);
+
+    let mut c = orders.relation_count(1) | orders.relation_count(3);
+    assert_eq!(2, customer_box.query(&mut c)?.count()?);
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_to_many_link_query() -> error::Result<()> {
+    let store = setup_store()?;
+    let mut teacher_box = store.get_box::<Teacher>()?;
+    let mut student_box = store.get_box::<Student>()?;
+    teacher_box.remove_all()?;
+    student_box.remove_all()?;
+
+    let StudentConditionFactory {
+        teachers, grade, ..
+    } = new_student_condition_factory();
+    let TeacherConditionFactory { name, .. } = new_teacher_condition_factory();
+    let relation_id = teachers.relation_id().expect("standalone relation");
+
+    let mut new_teacher = |name: &str, subject: &str| {
+        teacher_box.put(&mut Teacher {
+            id: 0,
+            name: name.to_string(),
+            subject: subject.to_string(),
+        })
+    };
+    let smith = new_teacher("Smith", "math")?;
+    let jones = new_teacher("Jones", "art")?;
+
+    let mut new_student = |name: &str, grade: i32| {
+        student_box.put(&mut Student {
+            id: 0,
+            name: name.to_string(),
+            grade,
+            teachers: ToMany::new(),
+        })
+    };
+    let alice = new_student("Alice", 1)?;
+    let bob = new_student("Bob", 2)?;
+    let carol = new_student("Carol", 2)?;
+
+    student_box.rel_put(relation_id, alice, smith)?;
+    student_box.rel_put(relation_id, bob, smith)?;
+    student_box.rel_put(relation_id, bob, jones)?;
+    student_box.rel_put(relation_id, carol, jones)?;
+
+    // students with a teacher named Smith
+    let mut c = teachers.with(name.eq("Smith".to_string()));
+    let mut ids: Vec<u64> = student_box.query(&mut c)?.find_ids()?;
+    ids.sort();
+    assert_eq!(vec![alice, bob], ids);
+
+    let mut c = grade.eq(2) & teachers.with(name.eq("Smith".to_string()));
+    assert_eq!(vec![bob], student_box.query(&mut c)?.find_ids()?);
+
+    // teachers of the students in grade 1
+    let mut c = teachers.backlink().with(grade.eq(1));
+    assert_eq!(vec![smith], teacher_box.query(&mut c)?.find_ids()?);
+
+    Ok(())
+}
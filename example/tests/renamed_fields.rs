@@ -2,7 +2,7 @@ use example::{make_factory_map, make_model, RenamedFieldsEntity};
 use objectbox::error;
 use objectbox::traits::{self, IdExt};
 use objectbox::{opt::Opt, store::Store};
-use std::rc;
+use std::sync;
 
 use serial_test::serial;
 
@@ -18,7 +18,7 @@ fn test_renamed_fields_put_and_get() -> error::Result<()> {
 
     let trait_map = make_factory_map();
     let factory = trait_map
-        .get::<rc::Rc<dyn traits::EntityFactoryExt<RenamedFieldsEntity>>>()
+        .get::<sync::Arc<dyn traits::EntityFactoryExt<RenamedFieldsEntity>>>()
         .unwrap()
         .clone();
 
@@ -60,7 +60,7 @@ fn test_renamed_fields_put_many_and_get_all() -> error::Result<()> {
 
     let trait_map = make_factory_map();
     let factory = trait_map
-        .get::<rc::Rc<dyn traits::EntityFactoryExt<RenamedFieldsEntity>>>()
+        .get::<sync::Arc<dyn traits::EntityFactoryExt<RenamedFieldsEntity>>>()
         .unwrap()
         .clone();
 
@@ -112,7 +112,7 @@ fn test_renamed_fields_update() -> error::Result<()> {
 
     let trait_map = make_factory_map();
     let factory = trait_map
-        .get::<rc::Rc<dyn traits::EntityFactoryExt<RenamedFieldsEntity>>>()
+        .get::<sync::Arc<dyn traits::EntityFactoryExt<RenamedFieldsEntity>>>()
         .unwrap()
         .clone();
 
@@ -0,0 +1,81 @@
+use example::{make_factory_map, make_model, Entity3};
+use objectbox::{error, opt::Opt, store::Store};
+use std::sync::Arc;
+use std::thread;
+
+use serial_test::serial;
+
+fn setup_store() -> error::Result<Store> {
+    let mut model = make_model();
+    let opt = Opt::from_model(&mut model)?;
+    let trait_map = make_factory_map();
+    Store::new(opt, trait_map)
+}
+
+fn put_hellos(store: &Store, n: usize) -> error::Result<()> {
+    let mut box3 = store.get_box::<Entity3>()?;
+    for i in 0..n {
+        box3.put(&mut Entity3 {
+            id: 0,
+            hello: format!("hello {i}"),
+        })?;
+    }
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_arc_store_across_threads() -> error::Result<()> {
+    let store = Arc::new(setup_store()?);
+    store.get_box::<Entity3>()?.remove_all()?;
+
+    let workers: Vec<_> = (0..4)
+        .map(|_| {
+            let store = Arc::clone(&store);
+            thread::spawn(move || put_hellos(&store, 10))
+        })
+        .collect();
+    for w in workers {
+        w.join().expect("worker panicked")?;
+    }
+
+    assert_eq!(40, store.get_box::<Entity3>()?.count()?);
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_try_clone_outlives_original() -> error::Result<()> {
+    let store = setup_store()?;
+    store.get_box::<Entity3>()?.remove_all()?;
+
+    let clone = store.try_clone()?;
+    assert_eq!(store.id(), clone.id());
+    let worker = thread::spawn(move || -> error::Result<u64> {
+        put_hellos(&clone, 3)?;
+        clone.get_box::<Entity3>()?.count()
+    });
+    assert_eq!(3, worker.join().expect("worker panicked")?);
+
+    // dropping the clone did not close the store
+    assert_eq!(3, store.get_box::<Entity3>()?.count()?);
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_try_clone_after_original_is_dropped() -> error::Result<()> {
+    let store = setup_store()?;
+    store.get_box::<Entity3>()?.remove_all()?;
+
+    let clone = store.try_clone()?;
+    drop(store);
+
+    // the clone keeps the store open
+    put_hellos(&clone, 2)?;
+    assert_eq!(2, clone.get_box::<Entity3>()?.count()?);
+    let clone2 = clone.try_clone()?;
+    drop(clone);
+    assert_eq!(2, clone2.get_box::<Entity3>()?.count()?);
+    Ok(())
+}
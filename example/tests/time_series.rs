@@ -0,0 +1,61 @@
+use example::{make_factory_map, make_model, SensorReading};
+use objectbox::datetime::DateTime;
+use objectbox::{error, opt::Opt, store::Store};
+
+use serial_test::serial;
+
+fn setup_store() -> error::Result<Store> {
+    let mut model = make_model();
+    let opt = Opt::from_model(&mut model)?;
+    let trait_map = make_factory_map();
+    Store::new(opt, trait_map)
+}
+
+#[test]
+#[serial]
+#[ignore = "needs the time series edition of the native library"]
+fn test_time_series_min_max() -> error::Result<()> {
+    let store = setup_store()?;
+    let mut box_ = store.get_box::<SensorReading>()?;
+    box_.remove_all()?;
+
+    assert_eq!(None, box_.time_series_min_max()?);
+
+    let mut ids = Vec::new();
+    for (millis, value) in [(3000, 0.3), (1000, 0.1), (2000, 0.2), (5000, 0.5)] {
+        ids.push(box_.put(&mut SensorReading {
+            id: 0,
+            measured_at: DateTime::from_millis(millis),
+            value,
+        })?);
+    }
+
+    let (min, max) = box_.time_series_min_max()?.expect("readings");
+    assert_eq!((ids[1], DateTime::from_millis(1000)), min);
+    assert_eq!((ids[3], DateTime::from_millis(5000)), max);
+
+    let (min, max) = box_
+        .time_series_min_max_range(DateTime::from_millis(1500), DateTime::from_millis(4000))?
+        .expect("readings in range");
+    assert_eq!((ids[2], DateTime::from_millis(2000)), min);
+    assert_eq!((ids[0], DateTime::from_millis(3000)), max);
+
+    assert_eq!(
+        None,
+        box_.time_series_min_max_range(DateTime::from_millis(6000), DateTime::from_millis(7000))?
+    );
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_time_series_unit_mismatch() -> error::Result<()> {
+    let store = setup_store()?;
+    let box_ = store.get_box::<SensorReading>()?;
+
+    // the companion is a DateTime, not a DateTimeNano
+    assert!(box_.time_series_min_max_nanos().is_err());
+
+    Ok(())
+}
@@ -0,0 +1,128 @@
+use example::{
+    make_factory_map, make_model, new_student_condition_factory, Entity3, Student, Teacher,
+};
+use objectbox::relations::ToMany;
+use objectbox::{error, opt::Opt, store::Store};
+
+use serial_test::serial;
+
+fn setup_store() -> error::Result<Store> {
+    let mut model = make_model();
+    let opt = Opt::from_model(&mut model)?;
+    let trait_map = make_factory_map();
+    Store::new(opt, trait_map)
+}
+
+fn new_entity3(hello: &str) -> Entity3 {
+    Entity3 {
+        id: 0,
+        hello: hello.to_string(),
+    }
+}
+
+#[test]
+#[serial]
+fn test_write_tx_commits_on_ok() -> error::Result<()> {
+    let store = setup_store()?;
+    store.get_box::<Entity3>()?.remove_all()?;
+
+    let ids = store.write_tx(|tx| {
+        let mut box3 = tx.get_box::<Entity3>()?;
+        let mut e1 = new_entity3("one");
+        let mut e2 = new_entity3("two");
+        box3.put_many(vec![&mut e1, &mut e2])
+    })?;
+
+    assert_eq!(2, ids.len());
+    assert_eq!(2, store.get_box::<Entity3>()?.count()?);
+
+    let count = store.read_tx(|tx| tx.get_box::<Entity3>()?.count())?;
+    assert_eq!(2, count);
+
+    let first = store.read_tx(|tx| tx.get_box::<Entity3>()?.get(ids[0]))?;
+    assert_eq!("one", first.unwrap().hello);
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_write_tx_aborts_on_err() -> error::Result<()> {
+    let store = setup_store()?;
+    store.get_box::<Entity3>()?.remove_all()?;
+
+    let r: error::Result<()> = store.write_tx(|tx| {
+        let mut box3 = tx.get_box::<Entity3>()?;
+        box3.put(&mut new_entity3("rolled back"))?;
+        assert_eq!(1, box3.count()?);
+        error::Error::new_local("abort").as_result()
+    });
+
+    assert!(r.is_err());
+    assert!(store.get_box::<Entity3>()?.is_empty()?);
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_write_tx_aborts_on_panic() -> error::Result<()> {
+    let store = setup_store()?;
+    store.get_box::<Entity3>()?.remove_all()?;
+
+    let r = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        store.write_tx(|tx| -> error::Result<()> {
+            tx.get_box::<Entity3>()?.put(&mut new_entity3("rolled back"))?;
+            panic!("abort")
+        })
+    }));
+
+    assert!(r.is_err());
+    assert!(store.get_box::<Entity3>()?.is_empty()?);
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_write_tx_relations_roll_back_together() -> error::Result<()> {
+    let store = setup_store()?;
+    store.get_box::<Teacher>()?.remove_all()?;
+    store.get_box::<Student>()?.remove_all()?;
+    let relation_id = new_student_condition_factory()
+        .teachers
+        .relation_id()
+        .expect("standalone relation");
+
+    let (student, teacher) = store.write_tx(|tx| {
+        let teacher = tx.get_box::<Teacher>()?.put(&mut Teacher {
+            id: 0,
+            name: "Smith".to_string(),
+            subject: "math".to_string(),
+        })?;
+        let mut students = tx.get_box::<Student>()?;
+        let student = students.put(&mut Student {
+            id: 0,
+            name: "Alice".to_string(),
+            grade: 1,
+            teachers: ToMany::new(),
+        })?;
+        students.rel_put(relation_id, student, teacher)?;
+        assert_eq!(vec![teacher], students.rel_get_ids(relation_id, student)?);
+        Ok((student, teacher))
+    })?;
+
+    let r: error::Result<()> = store.write_tx(|tx| {
+        let mut students = tx.get_box::<Student>()?;
+        students.rel_remove(relation_id, student, teacher)?;
+        assert!(students.rel_get_ids(relation_id, student)?.is_empty());
+        students.remove_with_id(student)?;
+        error::Error::new_local("abort").as_result()
+    });
+    assert!(r.is_err());
+
+    // both the removal and the unlinking were rolled back
+    let ids = store.read_tx(|tx| tx.get_box::<Student>()?.rel_get_ids(relation_id, student))?;
+    assert_eq!(vec![teacher], ids);
+    Ok(())
+}
@@ -0,0 +1,94 @@
+use example::{make_factory_map, make_model};
+use objectbox::tree::{LeafValue, Tree, TreeOptionFlags, TreeOptions};
+use objectbox::{error, opt::Opt, r#box::PutMode, store::Store};
+use std::sync::mpsc;
+use std::time::Duration;
+
+use serial_test::serial;
+
+fn setup_store() -> error::Result<Store> {
+    let mut model = make_model();
+    let opt = Opt::from_model(&mut model)?;
+    let trait_map = make_factory_map();
+    Store::new(opt, trait_map)
+}
+
+#[test]
+#[serial]
+fn test_tree_put_get() -> error::Result<()> {
+    let store = setup_store()?;
+
+    let r = Tree::with_options(
+        &store,
+        TreeOptions::new().flags(TreeOptionFlags::ENFORCE_UNIQUE_PATH),
+    );
+    if !Tree::is_available() {
+        let err = r.err().expect("tree should not open");
+        assert_eq!(error::ErrorKind::FeatureNotAvailable, err.kind());
+        return Ok(());
+    }
+
+    // the example model does not declare the tree entities
+    let tree = match r {
+        Ok(tree) => tree,
+        Err(_) => return Ok(()),
+    };
+
+    assert_eq!(None, tree.get("config/ui/theme")?);
+
+    let id = tree.put("config/ui/theme", "dark")?;
+    tree.put("config/ui/scale", 1.5)?;
+    tree.put("config/retries", 3_i64)?;
+
+    assert_eq!(Some(LeafValue::from("dark")), tree.get("config/ui/theme")?);
+    assert_eq!(Some(LeafValue::Double(1.5)), tree.get("config/ui/scale")?);
+    assert_eq!(Some(LeafValue::Int(3)), tree.get("config/retries")?);
+
+    let cursor = tree.read()?;
+    assert_eq!(
+        Some("config/ui/theme".to_string()),
+        cursor.get_leaf_path(id)?
+    );
+    let leaves = cursor.get_child_leaves_info(Some("config"))?;
+    assert_eq!(3, leaves.len());
+    assert!(leaves.iter().any(|l| l.path == "config/ui/theme"));
+    drop(cursor);
+
+    // a write cursor without commit aborts
+    let cursor = tree.write()?;
+    cursor.put("config/ui/theme", "light")?;
+    drop(cursor);
+    assert_eq!(Some(LeafValue::from("dark")), tree.get("config/ui/theme")?);
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_tree_async() -> error::Result<()> {
+    let store = setup_store()?;
+    let tree = match Tree::new(&store) {
+        Ok(tree) => tree,
+        Err(_) => return Ok(()),
+    };
+
+    let (tx, rx) = mpsc::channel();
+    tree.put_async("async/value", 42_i64, PutMode::Put, move |r| {
+        tx.send(r).unwrap();
+    })?;
+    let id = rx
+        .recv_timeout(Duration::from_secs(5))
+        .expect("put callback")?;
+    assert_ne!(0, id);
+
+    let (tx, rx) = mpsc::channel();
+    tree.get_async("async/value", move |r| {
+        tx.send(r).unwrap();
+    })?;
+    let value = rx
+        .recv_timeout(Duration::from_secs(5))
+        .expect("get callback")?;
+    assert_eq!(Some(LeafValue::Int(42)), value);
+
+    Ok(())
+}
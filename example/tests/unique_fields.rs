@@ -40,7 +40,9 @@ fn uniqueness_tests() -> error::Result<()> {
     // pretend this is a new object
     entity.id = 0;
 
-    assert!(box1.put(&mut entity).is_err());
+    let err = box1.put(&mut entity).unwrap_err();
+    assert_eq!(err.kind(), error::ErrorKind::UniqueViolated);
+    assert!(err.code().is_some());
 
     Ok(())
 }
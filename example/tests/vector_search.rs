@@ -0,0 +1,77 @@
+use example::{
+    make_factory_map, make_model, new_city_condition_factory, City, CityConditionFactory,
+};
+use objectbox::{error, opt::Opt, store::Store};
+
+use serial_test::serial;
+
+fn setup_store() -> error::Result<Store> {
+    let mut model = make_model();
+    let opt = Opt::from_model(&mut model)?;
+    let trait_map = make_factory_map();
+    Store::new(opt, trait_map)
+}
+
+fn new_city(name: &str, lat: f32, long: f32) -> City {
+    City {
+        id: 0,
+        name: name.to_string(),
+        location: vec![lat, long],
+    }
+}
+
+#[test]
+#[serial]
+fn test_nearest_neighbors() -> error::Result<()> {
+    let store = setup_store()?;
+    let mut box1 = store.get_box::<City>()?;
+    box1.remove_all()?;
+
+    box1.put(&mut new_city("Berlin", 52.52, 13.40))?;
+    box1.put(&mut new_city("Hamburg", 53.55, 9.99))?;
+    box1.put(&mut new_city("Munich", 48.14, 11.58))?;
+    box1.put(&mut new_city("Madrid", 40.42, -3.70))?;
+
+    let stored = box1.get_all()?;
+    assert!(stored.iter().all(|c| c.location.len() == 2));
+
+    let CityConditionFactory { location, .. } = new_city_condition_factory();
+
+    // close to Berlin
+    let query = box1.query(&mut location.nearest_neighbors(vec![52.0, 13.0], 2))?;
+    let found = query.find_with_scores()?;
+    assert_eq!(2, found.len());
+    assert_eq!("Berlin", found[0].0.name);
+    assert!(found[0].1 <= found[1].1);
+
+    let ids = query.find_ids_with_scores()?;
+    assert_eq!(2, ids.len());
+    assert_eq!(found[0].0.id, ids[0].0);
+
+    // exact match has no distance
+    let query = box1.query(&mut location.nearest_neighbors(vec![40.42, -3.70], 1))?;
+    let found = query.find_with_scores()?;
+    assert_eq!(1, found.len());
+    assert_eq!("Madrid", found[0].0.name);
+    assert!(found[0].1.abs() < 1e-6);
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_nearest_neighbors_rejects_wrong_dimensions() -> error::Result<()> {
+    let store = setup_store()?;
+    let box1 = store.get_box::<City>()?;
+
+    let CityConditionFactory { location, .. } = new_city_condition_factory();
+
+    assert!(box1
+        .query(&mut location.nearest_neighbors(vec![52.0], 2))
+        .is_err());
+    assert!(box1
+        .query(&mut location.nearest_neighbors(vec![52.0, 13.0, 0.0], 2))
+        .is_err());
+
+    Ok(())
+}
@@ -0,0 +1,106 @@
+use example::{
+    make_factory_map, make_model, new_vectorentity_condition_factory, VectorEntity,
+    VectorEntityConditionFactory,
+};
+use objectbox::datetime::{DateTime, DateTimeNano};
+use objectbox::{error, opt::Opt, store::Store};
+
+use serial_test::serial;
+
+fn setup_store() -> error::Result<Store> {
+    let mut model = make_model();
+    let opt = Opt::from_model(&mut model)?;
+    let trait_map = make_factory_map();
+    Store::new(opt, trait_map)
+}
+
+fn new_vector_entity() -> VectorEntity {
+    VectorEntity {
+        id: 0,
+        t_vec_bool: vec![true, false, true],
+        t_vec_i16: vec![i16::MIN, -1, i16::MAX],
+        t_vec_u16: vec![0, u16::MAX],
+        t_vec_char: vec!['a', 'ß', '😀'],
+        t_vec_i32: vec![i32::MIN, 0, i32::MAX],
+        t_vec_u32: vec![1, u32::MAX],
+        t_vec_i64: vec![i64::MIN, 0, i64::MAX],
+        t_vec_u64: vec![2, u64::MAX],
+        t_vec_f32: vec![1.5, -2.25],
+        t_vec_f64: vec![std::f64::consts::PI, -0.5],
+        t_vec_date: vec![DateTime(0), DateTime(1_700_000_000_000)],
+        t_vec_date_nano: vec![DateTimeNano(1_700_000_000_000_000_001)],
+        t_vec_string: vec!["red".to_string(), "green".to_string()],
+        opt_vec_i16: Some(vec![7, 8]),
+        opt_vec_i64: None,
+        opt_vec_f64: Some(Vec::new()),
+        opt_vec_char: Some(vec!['x', 'y']),
+        opt_vec_date: Some(vec![DateTime(42)]),
+    }
+}
+
+#[test]
+#[serial]
+fn test_vector_types_round_trip() -> error::Result<()> {
+    let store = setup_store()?;
+    let mut box1 = store.get_box::<VectorEntity>()?;
+    box1.remove_all()?;
+
+    let mut e = new_vector_entity();
+    let id = box1.put(&mut e)?;
+    let read = box1.get(id)?.expect("entity not found");
+
+    assert_eq!(e.t_vec_bool, read.t_vec_bool);
+    assert_eq!(e.t_vec_i16, read.t_vec_i16);
+    assert_eq!(e.t_vec_u16, read.t_vec_u16);
+    assert_eq!(e.t_vec_char, read.t_vec_char);
+    assert_eq!(e.t_vec_i32, read.t_vec_i32);
+    assert_eq!(e.t_vec_u32, read.t_vec_u32);
+    assert_eq!(e.t_vec_i64, read.t_vec_i64);
+    assert_eq!(e.t_vec_u64, read.t_vec_u64);
+    assert_eq!(e.t_vec_f32, read.t_vec_f32);
+    assert_eq!(e.t_vec_f64, read.t_vec_f64);
+    assert_eq!(e.t_vec_date, read.t_vec_date);
+    assert_eq!(e.t_vec_date_nano, read.t_vec_date_nano);
+    assert_eq!(e.t_vec_string, read.t_vec_string);
+    assert_eq!(e.opt_vec_i16, read.opt_vec_i16);
+    assert_eq!(None, read.opt_vec_i64);
+    assert_eq!(e.opt_vec_f64, read.opt_vec_f64);
+    assert_eq!(e.opt_vec_char, read.opt_vec_char);
+    assert_eq!(e.opt_vec_date, read.opt_vec_date);
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_vector_types_query() -> error::Result<()> {
+    let store = setup_store()?;
+    let mut box1 = store.get_box::<VectorEntity>()?;
+    box1.remove_all()?;
+
+    let mut e1 = new_vector_entity();
+    let mut e2 = new_vector_entity();
+    e2.t_vec_string = vec!["blue".to_string()];
+    e2.t_vec_i64 = Vec::new();
+    box1.put(&mut e1)?;
+    box1.put(&mut e2)?;
+
+    let VectorEntityConditionFactory {
+        t_vec_string,
+        opt_vec_i64,
+        ..
+    } = new_vectorentity_condition_factory();
+
+    let query = box1.query(&mut t_vec_string.contains_element("green"))?;
+    let found = query.find()?;
+    assert_eq!(1, found.len());
+    assert_eq!(e1.t_vec_string, found[0].t_vec_string);
+
+    let query = box1.query(&mut t_vec_string.contains_element("yellow"))?;
+    assert_eq!(0, query.count()?);
+
+    let query = box1.query(&mut opt_vec_i64.is_null())?;
+    assert_eq!(2, query.count()?);
+
+    Ok(())
+}
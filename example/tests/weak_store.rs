@@ -0,0 +1,66 @@
+use example::{make_factory_map, make_model, Entity3};
+use objectbox::store::WeakStore;
+use objectbox::{error, opt::Opt, store::Store};
+
+use serial_test::serial;
+
+fn setup_store() -> error::Result<Store> {
+    let mut model = make_model();
+    let opt = Opt::from_model(&mut model)?;
+    let trait_map = make_factory_map();
+    Store::new(opt, trait_map)
+}
+
+#[test]
+#[serial]
+fn test_weak_store_upgrade() -> error::Result<()> {
+    let store = setup_store()?;
+    let weak = store.downgrade()?;
+
+    {
+        let upgraded = weak.upgrade().expect("store is open");
+        assert_eq!(store.id(), upgraded.id());
+        upgraded.get_box::<Entity3>()?.remove_all()?;
+    }
+    assert_eq!(0, store.get_box::<Entity3>()?.count()?);
+
+    // the weak reference does not keep the store open
+    drop(store);
+    assert!(weak.upgrade().is_none());
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_weak_store_by_id() -> error::Result<()> {
+    let store = setup_store()?;
+
+    let weak = WeakStore::by_id(store.id(), make_factory_map()).expect("store is open");
+    let upgraded = weak.upgrade().expect("store is open");
+    assert_eq!(store.id(), upgraded.id());
+    drop(upgraded);
+
+    let id = store.id();
+    drop(store);
+    assert!(weak.upgrade().is_none());
+    assert!(WeakStore::by_id(id, make_factory_map()).is_none());
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_weak_store_upgrade_outlives_original() -> error::Result<()> {
+    let store = setup_store()?;
+    let weak = store.downgrade()?;
+    let upgraded = weak.upgrade().expect("store is open");
+    drop(store);
+
+    // the upgraded handle keeps the store open
+    upgraded.get_box::<Entity3>()?.remove_all()?;
+    assert_eq!(0, upgraded.get_box::<Entity3>()?.count()?);
+    assert!(weak.upgrade().is_some());
+
+    drop(upgraded);
+    assert!(weak.upgrade().is_none());
+    Ok(())
+}
@@ -1,11 +1,10 @@
 use objectbox::c;
 use objectbox::flatbuffers;
-use objectbox::map;
 use objectbox::model;
 use objectbox::query::traits as qtraits;
 use objectbox::traits;
 use std::marker;
-use std::rc;
+use std::sync;
 impl traits::IdExt for self::Entity {
     fn get_id(&self) -> c::obx_id {
         self.id
@@ -189,12 +188,12 @@ pub fn make_model() -> model::Model {
         .last_property_id(15, 13546724117586603806)
         .last_entity_id(1, 11130647957223740996)
 }
-pub fn make_factory_map() -> map::AnyMap {
-    let mut map = map::AnyMap::new();
-    let f1 = rc::Rc::new(traits::Factory::<self::Entity> {
+pub fn make_factory_map() -> traits::FactoryMap {
+    let mut map = traits::FactoryMap::new();
+    let f1 = sync::Arc::new(traits::Factory::<self::Entity> {
         phantom_data: marker::PhantomData,
         schema_id: 1,
-    }) as rc::Rc<dyn traits::EntityFactoryExt<self::Entity>>;
+    }) as sync::Arc<dyn traits::EntityFactoryExt<self::Entity>>;
     map.insert(f1);
     map
 }
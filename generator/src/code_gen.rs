@@ -6,6 +6,7 @@ use std::path::PathBuf;
 use genco::fmt;
 use genco::prelude::*;
 
+use crate::model_json::is_scalar_vector_type;
 use crate::model_json::prop_type_to_impl_blanket;
 use crate::model_json::ModelEntity;
 use crate::model_json::ModelInfo;
@@ -34,7 +35,7 @@ trait CodeGenEntityExt {
     fn generate_id_trait(&self) -> Tokens<Rust>;
     fn generate_fb_trait(&self) -> Tokens<Rust>;
     fn generate_ob_trait(&self) -> Tokens<Rust>;
-    fn generate_query_trait_impls(&self) -> Tokens<Rust>;
+    fn generate_query_trait_impls(&self, model_info: &ModelInfo) -> Tokens<Rust>;
 }
 
 fn encode_flatten(
@@ -91,11 +92,11 @@ fn encode_flatten(
                     }
                 }
             }
-            ob_consts::OBXPropertyType_IntVector => {
+            t if is_scalar_vector_type(t) => {
+                // Use the pre-created vector offset from encode_to_fb_unnested
                 quote! {
-                    if let Some(ref val) = self.$name {
-                        let int_vec_$offset = builder.create_vector(val.as_slice());
-                        builder.push_slot_always($offset, int_vec_$offset);
+                    if let Some(v) = scalar_vec_$offset {
+                        builder.push_slot_always($offset, v);
                     }
                 }
             }
@@ -198,9 +199,9 @@ fn encode_flatten(
               builder.push_slot_always($offset, byte_vec_$offset);
             }
         }
-        ob_consts::OBXPropertyType_IntVector => {
+        t if is_scalar_vector_type(t) => {
             quote! {
-              builder.push_slot_always($offset, int_vec_$offset);
+              builder.push_slot_always($offset, scalar_vec_$offset);
             }
         }
         ob_consts::OBXPropertyType_String => {
@@ -297,10 +298,11 @@ fn encode_to_fb_unnested(prop: &ModelProperty, offset: usize) -> Tokens<Rust> {
                     });
                 }
             }
-            ob_consts::OBXPropertyType_IntVector => {
+            t if is_scalar_vector_type(t) => {
+                let slice = prop.vector_to_fb_slice(quote!(v));
                 quote! {
-                    let int_vec_$offset = self.$name.as_ref().map(|v| {
-                        builder.create_vector(v.as_slice())
+                    let scalar_vec_$offset = self.$name.as_ref().map(|v| {
+                        builder.create_vector($slice)
                     });
                 }
             }
@@ -330,9 +332,10 @@ fn encode_to_fb_unnested(prop: &ModelProperty, offset: usize) -> Tokens<Rust> {
               let byte_vec_$offset = builder.create_vector(&self.$name.as_slice());
             }
         }
-        ob_consts::OBXPropertyType_IntVector => {
+        t if is_scalar_vector_type(t) => {
+            let slice = prop.vector_to_fb_slice(quote!(self.$name));
             quote! {
-              let int_vec_$offset = builder.create_vector(&self.$name.as_slice());
+              let scalar_vec_$offset = builder.create_vector($slice);
             }
         }
         ob_consts::OBXPropertyType_String => {
@@ -437,6 +440,21 @@ impl CodeGenEntityExt for ModelEntity {
         let entity = &rust::import("self", &self.name);
 
         let schema_id = &rust::import("objectbox::c", "obx_schema_id");
+        let property_type = &rust::import("objectbox::c", "OBXPropertyType");
+
+        // Date or DateNano, checked by the time series lookups on Box
+        let id_companion_fn = match self
+            .properties
+            .iter()
+            .find(|p| p.flags.unwrap_or(0) & ob_consts::OBXPropertyFlags_ID_COMPANION != 0)
+        {
+            Some(p) => quote! {
+                fn get_id_companion_type(&self) -> Option<$property_type> {
+                  Some($(p.type_field))
+                }
+            },
+            None => quote!(),
+        };
 
         let destructured_props = self
             .properties
@@ -496,6 +514,8 @@ impl CodeGenEntityExt for ModelEntity {
               self.schema_id
             }
 
+            $id_companion_fn
+
             fn new_entity(&self) -> $entity {
               $entity {
                 $(for p in destructured_props join (, ) => $(p))
@@ -507,30 +527,59 @@ impl CodeGenEntityExt for ModelEntity {
         }
     }
 
-    fn generate_query_trait_impls(&self) -> Tokens<Rust> {
+    fn generate_query_trait_impls(&self, model_info: &ModelInfo) -> Tokens<Rust> {
         let entity = &rust::import("self", &self.name);
 
+        let target_entity_id = |target_name: &str| {
+            model_info
+                .entities
+                .iter()
+                .find(|e| e.name == target_name)
+                .map(|e| e.id.get_id())
+        };
+
+        // Link handles for ToOne and standalone ToMany relations,
+        // skipped when the target entity is not part of the model
+        let mut cf_links = Vec::<Tokens<Rust>>::new();
+        let mut cf_init_links = Vec::<Tokens<Rust>>::new();
+        for p in self.properties.iter().filter(|p| p.is_relation()) {
+            if let Some(target_id) = p.get_relation_target().and_then(target_entity_id) {
+                cf_links.push(p.to_condition_factory_link_key_value(entity));
+                cf_init_links.push(p.to_condition_factory_link_init(self.id.get_id(), target_id));
+            }
+        }
+        for r in self.relations.iter() {
+            if let Some(target_id) = target_entity_id(r.target_entity_name()) {
+                cf_links.push(r.to_condition_factory_key_value(entity));
+                cf_init_links.push(r.to_condition_factory_init(self.id.get_id(), target_id));
+            }
+        }
+
         let cf_props = self
             .properties
             .iter()
-            .map(|p| p.to_condition_factory_struct_key_value(entity));
+            .map(|p| p.to_condition_factory_struct_key_value(entity))
+            .chain(cf_links);
 
         let cf_init_props = self
             .properties
             .iter()
-            .map(|p| p.to_condition_factory_init_dyn(entity, self.id.get_id()));
+            .map(|p| p.to_condition_factory_init_dyn(entity, self.id.get_id()))
+            .chain(cf_init_links);
 
         let name = self.name.as_str();
         let name_lower_case = self.name.to_ascii_lowercase();
 
         // Normalize types for blanket impl dedup: Date/DateNano use I64Blanket (same as Long),
-        // Flex/IntVector use VecU8Blanket (same as ByteVector)
+        // Flex uses VecU8Blanket (same as ByteVector), scalar vectors
+        // other than FloatVector share VecBlanket
         let vec_type_field: Vec<ob_consts::OBXPropertyType> =
             self.properties.iter().map(|p| match p.type_field {
                 ob_consts::OBXPropertyType_Date | ob_consts::OBXPropertyType_DateNano
                     => ob_consts::OBXPropertyType_Long,
-                ob_consts::OBXPropertyType_Flex | ob_consts::OBXPropertyType_IntVector
-                    => ob_consts::OBXPropertyType_ByteVector,
+                ob_consts::OBXPropertyType_Flex => ob_consts::OBXPropertyType_ByteVector,
+                ob_consts::OBXPropertyType_FloatVector => ob_consts::OBXPropertyType_FloatVector,
+                t if is_scalar_vector_type(t) => ob_consts::OBXPropertyType_LongVector,
                 other => other,
             }).collect();
         let hash_set =
@@ -595,7 +644,7 @@ fn generate_model_fn(model_info: &ModelInfo) -> Tokens<Rust> {
             .iter()
             .filter_map(|r| {
                 // Look up the target entity ID
-                if let Some(&target_id_str) = entity_id_map.get(r.target_name.as_str()) {
+                if let Some(&target_id_str) = entity_id_map.get(r.target_entity_name()) {
                     let rel_id = r.id.as_comma_separated_str();
                     let target_id = target_id_str.as_comma_separated_str();
                     Some(quote! { .relation($rel_id, $target_id) })
@@ -667,10 +716,10 @@ fn generate_model_fn(model_info: &ModelInfo) -> Tokens<Rust> {
 }
 
 fn generate_factory_map_fn(model_info: &ModelInfo) -> Tokens<Rust> {
-    let any_map = &rust::import("objectbox::map", "AnyMap");
+    let factory_map = &rust::import("objectbox::traits", "FactoryMap");
     let factory = &rust::import("objectbox::traits", "Factory");
     let factory_helper = &rust::import("objectbox::traits", "EntityFactoryExt");
-    let rc = &rust::import("std::rc", "Rc");
+    let arc = &rust::import("std::sync", "Arc");
     let phantom = &rust::import("std::marker", "PhantomData");
 
     let tokens = &mut Tokens::<Rust>::new();
@@ -687,18 +736,18 @@ fn generate_factory_map_fn(model_info: &ModelInfo) -> Tokens<Rust> {
         }
         let entity_id_str = entity_id.as_str();
         let quote = quote! {
-          let f$(entity_id_str) = $rc::new($factory::<$entity> {
+          let f$(entity_id_str) = $arc::new($factory::<$entity> {
             phantom_data: $phantom,
             schema_id: $entity_id_str
-          }) as $rc<dyn $factory_helper<$entity>>;
+          }) as $arc<dyn $factory_helper<$entity>>;
           map.insert(f$entity_id_str);
         };
         tokens.append(quote);
     }
 
     quote! {
-      pub fn make_factory_map() -> $any_map {
-        let mut map = $any_map::new();
+      pub fn make_factory_map() -> $factory_map {
+        let mut map = $factory_map::new();
         $(tokens.clone())
         map
       }
@@ -713,7 +762,7 @@ impl CodeGenExt for ModelInfo {
             tokens.append(e.generate_id_trait());
             tokens.append(e.generate_fb_trait());
             tokens.append(e.generate_ob_trait());
-            tokens.append(e.generate_query_trait_impls());
+            tokens.append(e.generate_query_trait_impls(self));
         }
 
         tokens.append(generate_model_fn(self));
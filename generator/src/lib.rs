@@ -277,6 +277,12 @@ pub fn generate_assets(out_path: &PathBuf, target_dir: &PathBuf) {
                 let mut types_changed = false;
                 for p_before in e_before.properties {
                     if let Some(p_new) = p_map.get(p_before.name.as_str()) {
+                        // Vec<i32> used to be written with the LongVector constant
+                        if p_before.type_field == ob_consts::OBXPropertyType_LongVector
+                            && p_new.type_field == ob_consts::OBXPropertyType_IntVector
+                        {
+                            println!("cargo:warning=The type of {}.{} changes from 27 (LongVector) to 26 (IntVector),\nan existing store may reject the change, consider backing up the data and giving the property a new uid", e_before.name, p_before.name);
+                        }
                         flags_changed |= p_new.flags != p_before.flags;
                         types_changed |= p_new.type_field != p_before.type_field;
                         model_has_changed |= flags_changed || types_changed;
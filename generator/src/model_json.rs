@@ -153,8 +153,9 @@ pub struct ModelRelation {
     /// Target entity name (used during code generation, not serialized to model JSON)
     #[serde(skip)]
     pub target_name: String,
-    /// Rust type string for code generation (not serialized)
-    #[serde(skip)]
+    /// Rust type string for code generation, e.g. "ToMany<Teacher>"
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    #[serde(rename = "rustType")]
     pub rust_type: String,
 }
 
@@ -194,6 +195,44 @@ impl ModelRelation {
     pub fn struct_field_name(&self) -> &str {
         &self.name
     }
+
+    /// Target entity name, from target_name or rust_type: "ToMany<Teacher>" -> "Teacher"
+    pub(crate) fn target_entity_name(&self) -> &str {
+        if !self.target_name.is_empty() {
+            return &self.target_name;
+        }
+        self.rust_type
+            .strip_prefix("ToMany<")
+            .and_then(|s| s.strip_suffix('>'))
+            .unwrap_or_default()
+    }
+
+    /// Link handle for a standalone ToMany relation, to nest conditions on the target entity
+    pub(crate) fn to_condition_factory_key_value(
+        &self,
+        entity_name: &genco::lang::rust::Import,
+    ) -> Tokens<Rust> {
+        let link = &rust::import("objectbox::query::link", "Link");
+        let kind = &rust::import("objectbox::query::link", "ToManyLink");
+        let target_name = &rust::import("self", self.target_entity_name());
+        let name = &self.name;
+        quote! {
+            pub $name: $link<$entity_name, $target_name, $kind>,
+        }
+    }
+
+    pub(crate) fn to_condition_factory_init(
+        &self,
+        entity_id: Tokens<Rust>,
+        target_entity_id: Tokens<Rust>,
+    ) -> Tokens<Rust> {
+        let link = &rust::import("objectbox::query::link", "Link");
+        let name = &self.name;
+        let relation_id = &self.id.get_id();
+        quote! {
+            $name: $link::to_many($entity_id, $(relation_id), $target_entity_id),
+        }
+    }
 }
 
 impl ModelEntity {
@@ -255,11 +294,94 @@ pub struct ModelProperty {
     /// The target entity name for ToOne relations (e.g., "Customer")
     #[serde(skip)]
     pub relation_target: Option<String>,
+
+    /// HNSW vector index parameters, from #[hnsw(dimensions = .., distance = "..")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hnsw_params: Option<HnswParams>,
+}
+
+/// Same shape as the "hnswParams" of the Dart/Java model JSON
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HnswParams {
+    pub dimensions: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub neighbors_per_node: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub indexing_search_count: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub flags: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub distance_type: Option<ob_consts::OBXVectorDistanceType>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reparation_backlink_probability: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vector_cache_hint_size_kb: Option<u64>,
+}
+
+impl HnswParams {
+    /// Maps the #[hnsw(distance = "..")] value to OBXVectorDistanceType
+    pub fn distance_type_from_str(distance: &str) -> Option<ob_consts::OBXVectorDistanceType> {
+        match distance {
+            "euclidean" => Some(ob_consts::OBXVectorDistanceType_Euclidean),
+            "cosine" => Some(ob_consts::OBXVectorDistanceType_Cosine),
+            "dot_product" => Some(ob_consts::OBXVectorDistanceType_DotProduct),
+            "geo" => Some(ob_consts::OBXVectorDistanceType_Geo),
+            "dot_product_non_normalized" => {
+                Some(ob_consts::OBXVectorDistanceType_DotProductNonNormalized)
+            }
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_fluent_builder_invocation(&self) -> Tokens<Rust> {
+        let dimensions = self.dimensions;
+        let mut q: Tokens<Rust> = quote! {
+            .property_index_hnsw_dimensions($dimensions)
+        };
+        if let Some(v) = self.neighbors_per_node {
+            q.extend(quote!(.property_index_hnsw_neighbors_per_node($v)));
+        }
+        if let Some(v) = self.indexing_search_count {
+            q.extend(quote!(.property_index_hnsw_indexing_search_count($v)));
+        }
+        if let Some(v) = self.flags {
+            q.extend(quote!(.property_index_hnsw_flags($v)));
+        }
+        if let Some(v) = self.distance_type {
+            q.extend(quote!(.property_index_hnsw_distance_type($v)));
+        }
+        if let Some(v) = self.reparation_backlink_probability {
+            let v = format!("{v:?}");
+            q.extend(quote!(.property_index_hnsw_reparation_backlink_probability($v)));
+        }
+        if let Some(v) = self.vector_cache_hint_size_kb {
+            q.extend(quote!(.property_index_hnsw_vector_cache_hint_size_kb($v)));
+        }
+        q
+    }
 }
 
 /// OBXPropertyType for ToOne relations
 pub const OBXPropertyType_Relation: ob_consts::OBXPropertyType = 11;
 
+/// Vectors of fixed size elements, stored as a FlatBuffers vector of the element type.
+/// ByteVector (also used for Flex) and StringVector are handled separately.
+pub(crate) fn is_scalar_vector_type(type_field: ob_consts::OBXPropertyType) -> bool {
+    matches!(
+        type_field,
+        ob_consts::OBXPropertyType_BoolVector
+            | ob_consts::OBXPropertyType_ShortVector
+            | ob_consts::OBXPropertyType_CharVector
+            | ob_consts::OBXPropertyType_IntVector
+            | ob_consts::OBXPropertyType_LongVector
+            | ob_consts::OBXPropertyType_FloatVector
+            | ob_consts::OBXPropertyType_DoubleVector
+            | ob_consts::OBXPropertyType_DateVector
+            | ob_consts::OBXPropertyType_DateNanoVector
+    )
+}
+
 fn split_id(input: &str) -> (&str, &str) {
     let v: Vec<&str> = input.split(':').collect();
     (v[0], v[1])
@@ -282,9 +404,14 @@ impl ModelProperty {
         self.rust_type.starts_with("ToOne<")
     }
     
-    /// Get the target entity name for ToOne relations
+    /// Get the target entity name for ToOne relations,
+    /// from relation_target or rust_type: "ToOne<Customer>" -> "Customer"
     pub(crate) fn get_relation_target(&self) -> Option<&str> {
-        self.relation_target.as_deref()
+        self.relation_target.as_deref().or_else(|| {
+            self.rust_type
+                .strip_prefix("ToOne<")
+                .and_then(|s| s.strip_suffix('>'))
+        })
     }
     
     /// Get the original ToOne field name
@@ -316,6 +443,69 @@ impl ModelProperty {
         self.rust_field_name().to_string()
     }
 
+    fn is_unsigned(&self) -> bool {
+        self.flags
+            .map_or(false, |f| (f & ob_consts::OBXPropertyFlags_UNSIGNED) != 0)
+    }
+
+    /// The FlatBuffers element type of a scalar vector
+    pub(crate) fn vector_element_type(&self) -> Tokens<Rust> {
+        match self.type_field {
+            ob_consts::OBXPropertyType_BoolVector => quote!(bool),
+            ob_consts::OBXPropertyType_ShortVector if self.is_unsigned() => quote!(u16),
+            ob_consts::OBXPropertyType_ShortVector => quote!(i16),
+            // stored as UTF-16 code units
+            ob_consts::OBXPropertyType_CharVector => quote!(u16),
+            ob_consts::OBXPropertyType_IntVector if self.is_unsigned() => quote!(u32),
+            ob_consts::OBXPropertyType_IntVector => quote!(i32),
+            ob_consts::OBXPropertyType_LongVector if self.is_unsigned() => quote!(u64),
+            ob_consts::OBXPropertyType_LongVector => quote!(i64),
+            ob_consts::OBXPropertyType_FloatVector => quote!(f32),
+            ob_consts::OBXPropertyType_DoubleVector => quote!(f64),
+            ob_consts::OBXPropertyType_DateVector | ob_consts::OBXPropertyType_DateNanoVector => {
+                quote!(i64)
+            }
+            _ => panic!("Not a scalar vector type: {}", self.type_field),
+        }
+    }
+
+    /// Converts a scalar vector field (`vec` is a Vec<_> expression)
+    /// into a slice of its FlatBuffers element type
+    pub(crate) fn vector_to_fb_slice(&self, vec: Tokens<Rust>) -> Tokens<Rust> {
+        match self.type_field {
+            ob_consts::OBXPropertyType_CharVector => quote! {
+                $vec.iter()
+                    .flat_map(|c| c.encode_utf16(&mut [0u16; 2]).to_vec())
+                    .collect::<Vec<u16>>()
+                    .as_slice()
+            },
+            ob_consts::OBXPropertyType_DateVector | ob_consts::OBXPropertyType_DateNanoVector => {
+                quote! {
+                    $vec.iter().map(|d| d.0).collect::<Vec<i64>>().as_slice()
+                }
+            }
+            _ => quote!($vec.as_slice()),
+        }
+    }
+
+    /// Converts a FlatBuffers vector (`fb_vec`) back into the Vec<_> of the field
+    pub(crate) fn vector_from_fb(&self, fb_vec: Tokens<Rust>) -> Tokens<Rust> {
+        match self.type_field {
+            ob_consts::OBXPropertyType_CharVector => quote! {
+                std::char::decode_utf16($fb_vec.iter()).filter_map(|r| r.ok()).collect()
+            },
+            ob_consts::OBXPropertyType_DateVector => {
+                let dt_import = &rust::import("objectbox::datetime", "DateTime");
+                quote!($fb_vec.iter().map($dt_import).collect())
+            }
+            ob_consts::OBXPropertyType_DateNanoVector => {
+                let dtn_import = &rust::import("objectbox::datetime", "DateTimeNano");
+                quote!($fb_vec.iter().map($dtn_import).collect())
+            }
+            _ => quote!($fb_vec.iter().collect()),
+        }
+    }
+
     pub(crate) fn as_fluent_builder_invocation(&self) -> Tokens<Rust> {
         let flags = if let Some(f) = self.flags { f } else { 0 };
         let (id, uid) = split_id(&self.id);
@@ -331,21 +521,13 @@ impl ModelProperty {
         
         // For ToOne relations, add property_relation to specify the target entity
         if self.type_field == OBXPropertyType_Relation {
-            // Derive target entity from relation_target or rust_type: "ToOne<Customer>" -> "Customer"
-            let target = if let Some(ref target_name) = self.relation_target {
-                target_name.clone()
-            } else if self.rust_type.starts_with("ToOne<") && self.rust_type.ends_with(">") {
-                // Extract entity name from "ToOne<EntityName>"
-                self.rust_type[6..self.rust_type.len()-1].to_string()
-            } else {
-                // Fallback: can't determine target, this shouldn't happen
-                "Unknown".to_string()
-            };
+            // Fallback: can't determine target, this shouldn't happen
+            let target = self.get_relation_target().unwrap_or("Unknown");
             
             if let Some(ref index_id_str) = &self.index_id {
                 let (idx_id, idx_uid) = split_id(index_id_str);
                 q.extend(quote! {
-                    .property_relation($(quoted(target.as_str())), $idx_id, $idx_uid)
+                    .property_relation($(quoted(target)), $idx_id, $idx_uid)
                 });
             }
         } else if let Some(ii) = &self.index_id {
@@ -353,6 +535,9 @@ impl ModelProperty {
             q.extend(quote! {
                 .property_index($idx_id, $idx_uid)
             });
+            if let Some(hnsw) = &self.hnsw_params {
+                q.extend(hnsw.as_fluent_builder_invocation());
+            }
         }
         q
     }
@@ -377,15 +562,15 @@ impl ModelProperty {
         }
         
         match self.type_field {
+            t if is_scalar_vector_type(t) => quote! {
+                $name: Vec::new()
+            },
             ob_consts::OBXPropertyType_StringVector => quote! {
                 $name: Vec::<String>::new()
             },
             ob_consts::OBXPropertyType_ByteVector | ob_consts::OBXPropertyType_Flex => quote! {
                 $name: Vec::<u8>::new()
             },
-            ob_consts::OBXPropertyType_IntVector => quote! {
-                $name: Vec::<i32>::new()
-            },
             ob_consts::OBXPropertyType_String => quote! {
                 $name: String::from("")
             },
@@ -456,6 +641,14 @@ impl ModelProperty {
         // Для Optional полів використовуємо .map() замість .unwrap()
         if self.is_optional() {
             return match self.type_field {
+                t if is_scalar_vector_type(t) => {
+                    let elem = self.vector_element_type();
+                    let from_fb = self.vector_from_fb(quote!(fv));
+                    quote! {
+                        *$name = table.get::<$fuo<$fvec<$elem>>>($offset, None)
+                            .map(|fv| $from_fb);
+                    }
+                }
                 ob_consts::OBXPropertyType_StringVector => quote! {
                     *$name = table.get::<$fuo<$fvec<$fuo<&str>>>>($offset, None)
                         .map(|sv| sv.iter().map(|s| s.to_string()).collect());
@@ -464,10 +657,6 @@ impl ModelProperty {
                     *$name = table.get::<$fuo<$fvec<u8>>>($offset, None)
                         .map(|bv| bv.bytes().to_vec());
                 },
-                ob_consts::OBXPropertyType_IntVector => quote! {
-                    *$name = table.get::<$fuo<$fvec<i32>>>($offset, None)
-                        .map(|iv| iv.iter().collect());
-                },
                 ob_consts::OBXPropertyType_String => quote! {
                     *$name = table.get::<$fuo<&str>>($offset, None)
                         .map(|s| s.to_string());
@@ -540,6 +729,15 @@ impl ModelProperty {
         
         // Для не-Optional полів використовуємо існуючий код з .unwrap()
         match self.type_field {
+            t if is_scalar_vector_type(t) => {
+                let elem = self.vector_element_type();
+                let from_fb = self.vector_from_fb(quote!(fv));
+                quote! {
+                    if let Some(fv) = table.get::<$fuo<$fvec<$elem>>>($offset, None) {
+                        *$name = $from_fb;
+                    }
+                }
+            }
             ob_consts::OBXPropertyType_StringVector => quote! {
                 let fb_vec_$name = table.get::<$fuo<$fvec<$fuo<&str>>>>($offset, None);
                 if let Some(sv) = fb_vec_$name {
@@ -552,12 +750,6 @@ impl ModelProperty {
                     *$name = bv.bytes().to_vec();
                 }
             },
-            ob_consts::OBXPropertyType_IntVector => quote! {
-                let fb_vec_$name = table.get::<$fuo<$fvec<i32>>>($offset, None);
-                if let Some(iv) = fb_vec_$name {
-                    *$name = iv.iter().collect();
-                }
-            },
             // TODO research clear the buffer, and read the slice instead
             // TODO see what's faster
             ob_consts::OBXPropertyType_String => quote! {
@@ -641,6 +833,7 @@ impl ModelProperty {
             ob_consts::OBXPropertyType_Long => 1,
             ob_consts::OBXPropertyType_StringVector => 2,
             ob_consts::OBXPropertyType_ByteVector => 3,
+            t if is_scalar_vector_type(t) => 3,
             ob_consts::OBXPropertyType_String => 4,
             ob_consts::OBXPropertyType_Float => 5,
             ob_consts::OBXPropertyType_Int => 5,
@@ -676,6 +869,12 @@ impl ModelProperty {
             &rust::import("objectbox::query::traits", "VecU8Blanket").with_module_alias("qtraits");
         let type_string =
             &rust::import("objectbox::query::traits", "StringBlanket").with_module_alias("qtraits");
+        let type_float_vec =
+            &rust::import("objectbox::query::traits", "VecF32Blanket").with_module_alias("qtraits");
+        let type_vec =
+            &rust::import("objectbox::query::traits", "VecBlanket").with_module_alias("qtraits");
+        let type_string_vec = &rust::import("objectbox::query::traits", "VecStringBlanket")
+            .with_module_alias("qtraits");
         let name = self.rust_field_name();
         match self.type_field {
             ob_consts::OBXPropertyType_Double => quote! {
@@ -712,10 +911,51 @@ impl ModelProperty {
             ob_consts::OBXPropertyType_Date | ob_consts::OBXPropertyType_DateNano => quote! {
                 pub $name: Box<dyn $type_long<$entity_name>>,
             },
+            ob_consts::OBXPropertyType_FloatVector => quote! {
+                pub $name: Box<dyn $type_float_vec<$entity_name>>,
+            },
+            t if is_scalar_vector_type(t) => quote! {
+                pub $name: Box<dyn $type_vec<$entity_name>>,
+            },
+            ob_consts::OBXPropertyType_StringVector => quote! {
+                pub $name: Box<dyn $type_string_vec<$entity_name>>,
+            },
             _ => quote!(), // TODO refine this for the remaining types, no support for now
         }
     }
 
+    /// Link handle for a ToOne relation, to nest conditions on the target entity
+    pub(crate) fn to_condition_factory_link_key_value(
+        &self,
+        entity_name: &genco::lang::rust::Import,
+    ) -> Tokens<Rust> {
+        let link = &rust::import("objectbox::query::link", "Link");
+        let kind = &rust::import("objectbox::query::link", "ToOneLink");
+        match self.get_relation_target() {
+            Some(target) if self.is_relation() => {
+                let target_name = &rust::import("self", target);
+                let name = self.struct_field_name();
+                quote! {
+                    pub $name: $link<$entity_name, $target_name, $kind>,
+                }
+            }
+            _ => quote!(),
+        }
+    }
+
+    pub(crate) fn to_condition_factory_link_init(
+        &self,
+        entity_id: Tokens<Rust>,
+        target_entity_id: Tokens<Rust>,
+    ) -> Tokens<Rust> {
+        let link = &rust::import("objectbox::query::link", "Link");
+        let name = self.struct_field_name();
+        let property_id = &self.id.get_id();
+        quote! {
+            $name: $link::to_one($entity_id, $(property_id), $target_entity_id),
+        }
+    }
+
     pub(crate) fn to_condition_factory_init_dyn(
         &self,
         entity_name: &genco::lang::rust::Import,
@@ -723,6 +963,11 @@ impl ModelProperty {
     ) -> Tokens<Rust> {
         let ccb_fn = &rust::import("objectbox::query::traits", "create_condition_builder")
             .with_module_alias("qtraits");
+        let cvcb_fn = &rust::import(
+            "objectbox::query::traits",
+            "create_vector_condition_builder",
+        )
+        .with_module_alias("qtraits");
 
         let name = self.rust_field_name();
         let property_id = &self.id.get_id();
@@ -739,9 +984,19 @@ impl ModelProperty {
             | ob_consts::OBXPropertyType_Bool
             | ob_consts::OBXPropertyType_Byte
             | ob_consts::OBXPropertyType_Date
-            | ob_consts::OBXPropertyType_DateNano => quote! {
+            | ob_consts::OBXPropertyType_DateNano
+            | ob_consts::OBXPropertyType_StringVector => quote! {
                 $name: Box::new($ccb_fn::<$entity_name, $entity_id, $(property_id), $(self.type_field)>()),
             },
+            t if is_scalar_vector_type(t) => match &self.hnsw_params {
+                // nearest_neighbors checks the query vector length against the index
+                Some(hnsw) => quote! {
+                    $name: Box::new($cvcb_fn::<$entity_name, $entity_id, $(property_id), $(self.type_field), $(hnsw.dimensions)>()),
+                },
+                None => quote! {
+                    $name: Box::new($ccb_fn::<$entity_name, $entity_id, $(property_id), $(self.type_field)>()),
+                },
+            },
             _ => quote!(), // TODO refine this for the remaining types, no support for now
         }
     }
@@ -773,6 +1028,12 @@ pub(crate) fn prop_type_to_impl_blanket(
         &rust::import("objectbox::query::traits", "VecU8Blanket").with_module_alias("qtraits");
     let impl_string =
         &rust::import("objectbox::query::traits", "StringBlanket").with_module_alias("qtraits");
+    let impl_float_vec =
+        &rust::import("objectbox::query::traits", "VecF32Blanket").with_module_alias("qtraits");
+    let impl_vec =
+        &rust::import("objectbox::query::traits", "VecBlanket").with_module_alias("qtraits");
+    let impl_string_vec =
+        &rust::import("objectbox::query::traits", "VecStringBlanket").with_module_alias("qtraits");
 
     let cb =
         &rust::import("objectbox::query::traits", "ConditionBuilder").with_module_alias("qtraits");
@@ -787,9 +1048,7 @@ pub(crate) fn prop_type_to_impl_blanket(
                 impl $impl_long<$entity_name> for $cb<$entity_name> {}
             }
         }
-        ob_consts::OBXPropertyType_ByteVector
-        | ob_consts::OBXPropertyType_Flex
-        | ob_consts::OBXPropertyType_IntVector => {
+        ob_consts::OBXPropertyType_ByteVector | ob_consts::OBXPropertyType_Flex => {
             quote! {
                 impl $impl_byte_vec<$entity_name> for $cb<$entity_name> {}
             }
@@ -835,7 +1094,21 @@ pub(crate) fn prop_type_to_impl_blanket(
                 impl $impl_long<$entity_name> for $cb<$entity_name> {}
             }
         }
-        // ob_consts::OBXPropertyType_StringVector => 2,
+        ob_consts::OBXPropertyType_FloatVector => {
+            quote! {
+                impl $impl_float_vec<$entity_name> for $cb<$entity_name> {}
+            }
+        }
+        t if is_scalar_vector_type(t) => {
+            quote! {
+                impl $impl_vec<$entity_name> for $cb<$entity_name> {}
+            }
+        }
+        ob_consts::OBXPropertyType_StringVector => {
+            quote! {
+                impl $impl_string_vec<$entity_name> for $cb<$entity_name> {}
+            }
+        }
         _ => quote!(), // TODO refine this for the remaining types, no support for now
     }
 }
@@ -12,6 +12,7 @@ pub type OBXDebugFlags = u32;
 pub type OBXPutPaddingMode = u32;
 pub type OBXPutMode = u32;
 pub type OBXOrderFlags = u32;
+pub type OBXVectorDistanceType = u32;
 
 // pub const OBX_VERSION_MAJOR: u32 = 0;
 // pub const OBX_VERSION_MINOR: u32 = 18;
@@ -84,9 +85,17 @@ pub const OBXPropertyType_Date: OBXPropertyType = 10;
 pub const OBXPropertyType_Relation: OBXPropertyType = 11;
 pub const OBXPropertyType_DateNano: OBXPropertyType = 12;
 pub const OBXPropertyType_Flex: OBXPropertyType = 13;
+pub const OBXPropertyType_BoolVector: OBXPropertyType = 22;
 pub const OBXPropertyType_ByteVector: OBXPropertyType = 23;
-pub const OBXPropertyType_IntVector: OBXPropertyType = 27;
+pub const OBXPropertyType_ShortVector: OBXPropertyType = 24;
+pub const OBXPropertyType_CharVector: OBXPropertyType = 25;
+pub const OBXPropertyType_IntVector: OBXPropertyType = 26;
+pub const OBXPropertyType_LongVector: OBXPropertyType = 27;
+pub const OBXPropertyType_FloatVector: OBXPropertyType = 28;
+pub const OBXPropertyType_DoubleVector: OBXPropertyType = 29;
 pub const OBXPropertyType_StringVector: OBXPropertyType = 30;
+pub const OBXPropertyType_DateVector: OBXPropertyType = 31;
+pub const OBXPropertyType_DateNanoVector: OBXPropertyType = 32;
 pub const OBXEntityFlags_SYNC_ENABLED: OBXEntityFlags = 2;
 pub const OBXEntityFlags_SHARED_GLOBAL_IDS: OBXEntityFlags = 4;
 pub const OBXPropertyFlags_ID: OBXPropertyFlags = 1;
@@ -106,6 +115,12 @@ pub const OBXPropertyFlags_UNSIGNED: OBXPropertyFlags = 8192;
 pub const OBXPropertyFlags_ID_COMPANION: OBXPropertyFlags = 16384;
 pub const OBXPropertyFlags_UNIQUE_ON_CONFLICT_REPLACE: OBXPropertyFlags = 32768;
 pub const OBXPropertyFlags_EXPIRATION_TIME: OBXPropertyFlags = 65536;
+pub const OBXVectorDistanceType_Unknown: OBXVectorDistanceType = 0;
+pub const OBXVectorDistanceType_Euclidean: OBXVectorDistanceType = 1;
+pub const OBXVectorDistanceType_Cosine: OBXVectorDistanceType = 2;
+pub const OBXVectorDistanceType_DotProduct: OBXVectorDistanceType = 3;
+pub const OBXVectorDistanceType_Geo: OBXVectorDistanceType = 6;
+pub const OBXVectorDistanceType_DotProductNonNormalized: OBXVectorDistanceType = 10;
 // pub const OBXDebugFlags_LOG_TRANSACTIONS_READ: OBXDebugFlags = 1;
 // pub const OBXDebugFlags_LOG_TRANSACTIONS_WRITE: OBXDebugFlags = 2;
 // pub const OBXDebugFlags_LOG_QUERIES: OBXDebugFlags = 4;
@@ -1,4 +1,4 @@
-use objectbox_generator::{id, model_json};
+use objectbox_generator::{id, model_json, ob_consts as consts};
 use syn::{punctuated::Pair, DeriveInput};
 
 use crate::property::{ParsedField, Property, Relation};
@@ -72,6 +72,15 @@ impl Entity {
         if fields.is_empty() {
             panic!("Structs must have at least one attribute / property!");
         }
+
+        for (flag, attr) in [
+            (consts::OBXPropertyFlags_ID_COMPANION, "id_companion"),
+            (consts::OBXPropertyFlags_EXPIRATION_TIME, "expiration_time"),
+        ] {
+            if fields.iter().filter(|f| f.flags & flag != 0).count() > 1 {
+                panic!("{} has more than one #[{}] field", entity_name, attr);
+            }
+        }
         entity
     }
 
@@ -106,6 +115,7 @@ impl Entity {
                 rust_name,
                 relation_field: f.relation_field.clone(),
                 relation_target: f.relation_target.clone(),
+                hnsw_params: f.hnsw_params.clone(),
             };
             v.push(p);
         }
@@ -147,6 +147,15 @@ pub fn index(_attribute: TokenStream, input: TokenStream) -> TokenStream {
     input
 }
 
+/// HNSW vector index on a Vec<f32> field, for nearest neighbor search.
+/// Requires 'dimensions', accepts 'distance' ("euclidean", "cosine", "dot_product",
+/// "geo", "dot_product_non_normalized"), 'neighbors_per_node', 'indexing_search_count',
+/// 'flags', 'reparation_backlink_probability' and 'vector_cache_hint_size_kb'
+#[proc_macro_attribute]
+pub fn hnsw(_attribute: TokenStream, input: TokenStream) -> TokenStream {
+    input
+}
+
 /// Accepts 'uid'
 #[proc_macro_attribute]
 pub fn unique(_attribute: TokenStream, input: TokenStream) -> TokenStream {
@@ -159,6 +168,20 @@ pub fn backlink(_attribute: TokenStream, input: TokenStream) -> TokenStream {
     input
 }
 
+/// No required params, only on a DateTime or DateTimeNano field.
+/// Marks the time series timestamp that accompanies the id, max. one per entity
+#[proc_macro_attribute]
+pub fn id_companion(_attribute: TokenStream, input: TokenStream) -> TokenStream {
+    input
+}
+
+/// No required params, only on a DateTime or DateTimeNano field.
+/// The time after which the object may be removed by `Store::remove_expired`, max. one per entity
+#[proc_macro_attribute]
+pub fn expiration_time(_attribute: TokenStream, input: TokenStream) -> TokenStream {
+    input
+}
+
 /// No required params
 #[proc_macro_attribute]
 pub fn transient(_attribute: TokenStream, input: TokenStream) -> TokenStream {
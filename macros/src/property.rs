@@ -1,6 +1,7 @@
 use std::option::Option;
 
 use objectbox_generator::id;
+use objectbox_generator::model_json::HnswParams;
 use objectbox_generator::ob_consts as consts;
 
 use crate::path_visitor::get_idents_from_path;
@@ -57,6 +58,9 @@ pub struct Property {
     // ToOne relation fields
     pub relation_field: Option<String>,   // Original ToOne field name (e.g., "customer")
     pub relation_target: Option<String>,  // Target entity name (e.g., "Customer")
+
+    // HNSW vector index from #[hnsw(dimensions = N, distance = "...")]
+    pub hnsw_params: Option<HnswParams>,
 }
 
 impl Property {
@@ -71,6 +75,7 @@ impl Property {
             rust_type: String::new(),
             relation_field: None,
             relation_target: None,
+            hnsw_params: None,
         }
     }
     
@@ -101,6 +106,45 @@ impl Property {
         (obx_property_type, obx_property_flags)
     }
 
+    /// Parse a single #[hnsw(...)] parameter
+    fn scan_hnsw_param(mnv: &syn::MetaNameValue, hnsw: &mut HnswParams) {
+        let key = match mnv.path.get_ident() {
+            Some(ident) => ident.to_string(),
+            None => return,
+        };
+        match (key.as_str(), &mnv.lit) {
+            ("dimensions", syn::Lit::Int(li)) => {
+                hnsw.dimensions = li.base10_parse::<u64>().unwrap_or(0);
+            }
+            ("neighbors_per_node", syn::Lit::Int(li)) => {
+                hnsw.neighbors_per_node = li.base10_parse::<u32>().ok();
+            }
+            ("indexing_search_count", syn::Lit::Int(li)) => {
+                hnsw.indexing_search_count = li.base10_parse::<u32>().ok();
+            }
+            ("flags", syn::Lit::Int(li)) => {
+                hnsw.flags = li.base10_parse::<u32>().ok();
+            }
+            ("distance", syn::Lit::Str(ls)) => {
+                let distance = ls.value();
+                match HnswParams::distance_type_from_str(&distance) {
+                    Some(dt) => hnsw.distance_type = Some(dt),
+                    None => panic!(
+                        "Unknown hnsw distance: '{}'. Use 'euclidean', 'cosine', 'dot_product', 'geo' or 'dot_product_non_normalized'.",
+                        distance
+                    ),
+                }
+            }
+            ("reparation_backlink_probability", syn::Lit::Float(lf)) => {
+                hnsw.reparation_backlink_probability = lf.base10_parse::<f32>().ok();
+            }
+            ("vector_cache_hint_size_kb", syn::Lit::Int(li)) => {
+                hnsw.vector_cache_hint_size_kb = li.base10_parse::<u64>().ok();
+            }
+            _ => panic!("Unknown or invalid hnsw parameter: '{}'", key),
+        }
+    }
+
     /// Parse a syn::Field and return either a Property, Relation, or None
     pub(crate) fn from_syn_field(field: &syn::Field) -> Option<ParsedField> {
         let mut property = Property::new();
@@ -115,6 +159,7 @@ impl Property {
             rust_type,
             relation_field,
             relation_target,
+            hnsw_params,
         } = &mut property;
 
         if let Some(ident) = &field.ident {
@@ -142,6 +187,7 @@ impl Property {
                 // Track which attribute we're processing (for context-sensitive params)
                 let mut is_id_attr = false;
                 let mut is_index_or_unique_attr = false;
+                let mut is_hnsw_attr = false;
 
                 if let Some(attr_path_ident) = a.path.get_ident() {
                     let attr_name: &str = &attr_path_ident.to_string();
@@ -170,6 +216,20 @@ impl Property {
                             *obx_property_flags |= consts::OBXPropertyFlags_UNIQUE;
                             *index_id = Some("0:0".to_owned());
                         }
+                        "hnsw" => {
+                            // HNSW is a special kind of index, for vector search
+                            is_hnsw_attr = true;
+                            *hnsw_params = Some(HnswParams::default());
+                            if index_id.is_none() {
+                                *index_id = Some("0:0".to_owned());
+                            }
+                        }
+                        "id_companion" => {
+                            *obx_property_flags |= consts::OBXPropertyFlags_ID_COMPANION;
+                        }
+                        "expiration_time" => {
+                            *obx_property_flags |= consts::OBXPropertyFlags_EXPIRATION_TIME;
+                        }
                         "backlink" => {} // TODO: implement backlinks
                         "property" => {}
                         _ => {
@@ -190,6 +250,12 @@ impl Property {
                             meta_list.nested.into_iter().for_each(|nm| {
                                 match nm {
                                     syn::NestedMeta::Meta(syn::Meta::NameValue(mnv)) => {
+                                        if is_hnsw_attr {
+                                            if let Some(hnsw) = hnsw_params.as_mut() {
+                                                Self::scan_hnsw_param(&mnv, hnsw);
+                                            }
+                                            return;
+                                        }
                                         // Route id/uid to index_id_uid when inside #[index] or #[unique],
                                         // otherwise to the property id.
                                         if is_index_or_unique_attr {
@@ -368,6 +434,32 @@ impl Property {
                 }
             }
 
+            let is_date = *obx_property_type == consts::OBXPropertyType_Date
+                || *obx_property_type == consts::OBXPropertyType_DateNano;
+            for (flag, attr) in [
+                (consts::OBXPropertyFlags_ID_COMPANION, "id_companion"),
+                (consts::OBXPropertyFlags_EXPIRATION_TIME, "expiration_time"),
+            ] {
+                if *obx_property_flags & flag != 0 && !is_date {
+                    panic!(
+                        "#[{}] on {} requires a DateTime or DateTimeNano field",
+                        attr, field_name
+                    );
+                }
+            }
+
+            if let Some(hnsw) = hnsw_params {
+                if *obx_property_type != consts::OBXPropertyType_FloatVector {
+                    panic!(
+                        "#[hnsw] on {} requires a Vec<f32> or Option<Vec<f32>> field",
+                        field_name
+                    );
+                }
+                if hnsw.dimensions == 0 {
+                    panic!("#[hnsw] on {} requires dimensions = N (N > 0)", field_name);
+                }
+            }
+
             return Some(ParsedField::Property(property));
         }
         None
@@ -391,7 +483,15 @@ impl Property {
             "String" => consts::OBXPropertyType_String,
             "VecString" => consts::OBXPropertyType_StringVector,
             "Vecu8" => consts::OBXPropertyType_ByteVector,
-            "Veci32" => consts::OBXPropertyType_IntVector,
+            "Vecbool" => consts::OBXPropertyType_BoolVector,
+            "Veci16" | "Vecu16" => consts::OBXPropertyType_ShortVector,
+            "Vecchar" => consts::OBXPropertyType_CharVector,
+            "Veci32" | "Vecu32" => consts::OBXPropertyType_IntVector,
+            "Veci64" | "Vecu64" => consts::OBXPropertyType_LongVector,
+            "Vecf32" => consts::OBXPropertyType_FloatVector,
+            "Vecf64" => consts::OBXPropertyType_DoubleVector,
+            "VecDateTime" => consts::OBXPropertyType_DateVector,
+            "VecDateTimeNano" => consts::OBXPropertyType_DateNanoVector,
             // DateTime types (analogous to Dart's PropertyType.dateUtc / dateNanoUtc)
             "DateTime" => consts::OBXPropertyType_Date,
             "DateTimeNano" => consts::OBXPropertyType_DateNano,
@@ -403,6 +503,8 @@ impl Property {
     fn type_str_to_unsigned_flag(type_str: &str) -> consts::OBXPropertyFlags {
         match type_str {
             "u8" | "u16" | "u32" | "u64" => consts::OBXPropertyFlags_UNSIGNED,
+            // Vec<u8> is the ByteVector, which has no sign
+            "Vecu16" | "Vecu32" | "Vecu64" => consts::OBXPropertyFlags_UNSIGNED,
             _ => 0,
         }
     }
@@ -0,0 +1,149 @@
+#![allow(dead_code)]
+use std::ffi::CString;
+use std::marker::PhantomData;
+
+use crate::c::{self, *};
+use crate::error::{self, Error, ErrorKind};
+use crate::store::Store;
+
+/// The embedded Admin web UI (object browser), served until dropped.
+/// Must not outlive the Store it was started for.
+///
+/// ```rust,ignore
+/// let admin = Admin::builder()
+///     .store(&store)
+///     .bind("127.0.0.1:8081")
+///     .threads(2)
+///     .start()?;
+/// println!("http://127.0.0.1:{}", admin.port());
+/// ```
+pub struct Admin<'s> {
+    obx_admin: *mut OBX_admin,
+    phantom_data: PhantomData<&'s Store>,
+}
+
+impl Drop for Admin<'_> {
+    fn drop(&mut self) {
+        if !self.obx_admin.is_null() {
+            if let Err(err) = c::call(unsafe { obx_admin_close(self.obx_admin) }) {
+                eprintln!("Error: admin: {err}");
+            }
+            self.obx_admin = std::ptr::null_mut();
+        }
+    }
+}
+
+impl<'s> Admin<'s> {
+    pub fn builder() -> AdminBuilder<'s> {
+        AdminBuilder::default()
+    }
+
+    /// The port the http server listens on, e.g. when bound to port 0
+    pub fn port(&self) -> u16 {
+        unsafe { obx_admin_port(self.obx_admin) }
+    }
+
+    /// Whether the native library comes with the Admin web UI
+    pub fn is_available() -> bool {
+        unsafe { obx_has_feature(OBXFeature_Admin) }
+    }
+}
+
+#[derive(Default)]
+pub struct AdminBuilder<'s> {
+    store: Option<&'s Store>,
+    bind: Option<String>,
+    threads: Option<usize>,
+    unsecured_no_authentication: Option<bool>,
+    user_management: Option<bool>,
+    log_requests: Option<bool>,
+}
+
+impl<'s> AdminBuilder<'s> {
+    /// Required, the open store to browse
+    pub fn store(mut self, store: &'s Store) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    /// Address and port, defaults to "127.0.0.1:8081", use port 0 for any free port
+    pub fn bind(mut self, address: &str) -> Self {
+        self.bind = Some(address.to_string());
+        self
+    }
+
+    /// Number of http server threads
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.threads = Some(threads);
+        self
+    }
+
+    /// Skip the login, only for development with local-only access
+    pub fn unsecured_no_authentication(mut self, value: bool) -> Self {
+        self.unsecured_no_authentication = Some(value);
+        self
+    }
+
+    pub fn user_management(mut self, value: bool) -> Self {
+        self.user_management = Some(value);
+        self
+    }
+
+    pub fn log_requests(mut self, value: bool) -> Self {
+        self.log_requests = Some(value);
+        self
+    }
+
+    /// Fails with ErrorKind::FeatureNotAvailable if the native library
+    /// comes without the Admin web UI
+    pub fn start(self) -> error::Result<Admin<'s>> {
+        if !Admin::is_available() {
+            return Err(Error::new_local_kind(
+                ErrorKind::FeatureNotAvailable,
+                "Error: admin: not available in this native library",
+            ));
+        }
+        let store = self
+            .store
+            .ok_or_else(|| Error::new_local("Error: admin: a store is required"))?;
+
+        let opt = c::new_mut(unsafe { obx_admin_opt() })?;
+        if let Err(err) = self.apply(opt, store) {
+            // only unused options are freed here, obx_admin frees them otherwise
+            unsafe { obx_admin_opt_free(opt) };
+            return Err(err);
+        }
+        let obx_admin = c::new_mut(unsafe { obx_admin(opt) })?;
+        Ok(Admin {
+            obx_admin,
+            phantom_data: PhantomData,
+        })
+    }
+
+    fn apply(&self, opt: *mut OBX_admin_options, store: &Store) -> error::Result<()> {
+        c::call(unsafe { obx_admin_opt_store(opt, store.obx_store) })?;
+        if let Some(address) = &self.bind {
+            let uri = if address.contains("://") {
+                address.clone()
+            } else {
+                format!("http://{address}")
+            };
+            let c_str = CString::new(uri)
+                .map_err(|e| Error::new_local(&format!("Invalid bind address: {}", e)))?;
+            c::call(unsafe { obx_admin_opt_bind(opt, c_str.as_ptr()) })?;
+        }
+        if let Some(threads) = self.threads {
+            c::call(unsafe { obx_admin_opt_num_threads(opt, threads) })?;
+        }
+        if let Some(value) = self.unsecured_no_authentication {
+            c::call(unsafe { obx_admin_opt_unsecured_no_authentication(opt, value) })?;
+        }
+        if let Some(value) = self.user_management {
+            c::call(unsafe { obx_admin_opt_user_management(opt, value) })?;
+        }
+        if let Some(value) = self.log_requests {
+            c::call(unsafe { obx_admin_opt_log_requests(opt, value) })?;
+        }
+        Ok(())
+    }
+}
@@ -1,7 +1,13 @@
 #![allow(dead_code)]
+use std::sync::Arc;
+
+use flatbuffers::FlatBufferBuilder;
+
+use crate::r#box::PutMode;
+use crate::traits::{EntityFactoryExt, OBBlanket};
+use crate::util::{ConstVoidPtr, ToCVoid};
 use crate::{c, error};
 
-// TODO required for putAsync and putQueued
 pub(crate) struct Async {
     pub(crate) obx_async: *mut c::OBX_async,
     ptr_closed: bool,
@@ -19,12 +25,13 @@ impl Drop for Async {
 }
 
 impl Async {
-    // TODO test
+    /// The shared async queue of the box, which is owned by the box,
+    /// so it must not be closed here.
     pub fn from_box(obx_box: *mut c::OBX_box) -> error::Result<Self> {
         unsafe {
             c::new_mut(c::obx_async(obx_box)).map(|ptr| Async {
                 obx_async: ptr,
-                ptr_closed: false,
+                ptr_closed: true,
             })
         }
     }
@@ -55,39 +62,103 @@ impl Async {
         c::call(unsafe { c::obx_async_close(self.obx_async) })
     }
 
-    // TODO finish
-    /*
-    pub(crate) fn put5(&mut self, id: c::obx_id, data: ConstVoidPtr, size: usize, mode: c::OBXPutMode) -> error::Result<c::obx_id> {
-      // TODO depending on the state of the object determine the mode (PUT, INSERT, UPDATE)
-      // TODO if this is a fresh new object, get an id
-      c::call(unsafe { c::obx_async_put5(self.obx_async, id, data, size, mode) }).err();
-      // TODO return this error::Result<id>
+    pub(crate) fn put5(
+        &mut self,
+        id: c::obx_id,
+        data: ConstVoidPtr,
+        size: usize,
+        mode: c::OBXPutMode,
+    ) -> error::Result<()> {
+        c::call(unsafe { c::obx_async_put5(self.obx_async, id, data, size, mode) })
     }
-    */
 
-    /*
-    // fn put(&self, async_: *mut OBX_async, id: obx_id, data: *const ::std::os::raw::c_void, size: usize) -> obx_err {
-    //     unsafe { obx_async_put(async_, id, data, size) }
-    // }
+    /// Returns the id assigned to the object
+    pub(crate) fn put_object4(
+        &mut self,
+        data: &mut Vec<u8>,
+        mode: c::OBXPutMode,
+    ) -> error::Result<c::obx_id> {
+        c::get_result_from_id(unsafe {
+            c::obx_async_put_object4(
+                self.obx_async,
+                data.as_mut_ptr() as *mut ::std::os::raw::c_void,
+                data.len(),
+                mode,
+            )
+        })
+    }
+}
 
-    // fn insert(&self, async_: *mut OBX_async, id: obx_id, data: *const ::std::os::raw::c_void, size: usize) -> obx_err {
-    //     unsafe { obx_async_insert(async_, id, data, size) }
-    // }
+/// Enqueues writes, which are committed in the background.
+/// Call `Store::await_async_completion` to wait until the queue is flushed.
+pub struct AsyncBox<'a, T: OBBlanket> {
+    helper: Arc<dyn EntityFactoryExt<T>>,
+    obx_box: *mut c::OBX_box,
+    async_: Async,
+    builder: FlatBufferBuilder<'a>,
+}
 
-    // fn update(&self, async_: *mut OBX_async, id: obx_id, data: *const ::std::os::raw::c_void, size: usize) -> obx_err {
-    //     unsafe { obx_async_update(async_, id, data, size) }
-    // }
+impl<T: OBBlanket> AsyncBox<'_, T> {
+    pub(crate) fn new(
+        obx_box: *mut c::OBX_box,
+        helper: Arc<dyn EntityFactoryExt<T>>,
+        async_: Async,
+    ) -> Self {
+        AsyncBox {
+            helper,
+            obx_box,
+            async_,
+            builder: FlatBufferBuilder::new(),
+        }
+    }
 
-    // fn put_object(&self, async_: *mut OBX_async, data: *mut ::std::os::raw::c_void, size: usize) -> obx_id {
-    //     unsafe { obx_async_put_object(async_, data, size) }
-    // }
+    // The id is reserved right away, so new objects know their id
+    // before the write is committed.
+    fn put_with_mode(&mut self, object: &mut T, mode: PutMode) -> error::Result<c::obx_id> {
+        let id = c::get_result_from_id(unsafe {
+            c::obx_box_id_for_put(self.obx_box, object.get_id())
+        })?;
+        object.set_id(id);
 
-    // fn put_object4(&self, async_: *mut OBX_async, data: *mut ::std::os::raw::c_void, size: usize, mode: OBXPutMode) -> obx_id {
-    //     unsafe { obx_async_put_object4(async_, data, size, mode) }
-    // }
+        object.flatten(&mut self.builder);
+        let data = Vec::from(self.builder.finished_data());
+        self.async_
+            .put5(id, data.to_const_c_void(), data.len(), mode.to_obx())
+            .map(|_| id)
+    }
 
-    // fn insert_object(&self, async_: *mut OBX_async, data: *mut ::std::os::raw::c_void, size: usize) -> obx_id {
-    //     unsafe { obx_async_insert_object(async_, data, size) }
-    // }
-    */
+    /// Inserts or updates, new objects get an id assigned
+    pub fn put(&mut self, object: &mut T) -> error::Result<c::obx_id> {
+        self.put_with_mode(object, PutMode::Put)
+    }
+
+    /// Fails in the background if the id already exists
+    pub fn insert(&mut self, object: &mut T) -> error::Result<c::obx_id> {
+        self.put_with_mode(object, PutMode::Insert)
+    }
+
+    /// Fails in the background if the id does not exist (yet)
+    pub fn update(&mut self, object: &mut T) -> error::Result<c::obx_id> {
+        if object.get_id() == 0 {
+            return error::Error::new_local_kind(
+                error::ErrorKind::IdNotFound,
+                "Error: async: can't update an object without an id",
+            )
+            .as_result();
+        }
+        self.put_with_mode(object, PutMode::Update)
+    }
+
+    /// Lets the native side assign the id and write it into the enqueued data
+    pub fn put_object(&mut self, object: &mut T) -> error::Result<c::obx_id> {
+        object.flatten(&mut self.builder);
+        let mut data = Vec::from(self.builder.finished_data());
+        let id = self.async_.put_object4(&mut data, PutMode::Put.to_obx())?;
+        object.set_id(id);
+        Ok(id)
+    }
+
+    pub fn remove(&mut self, id: c::obx_id) -> error::Result<bool> {
+        self.async_.remove_with_id(id)
+    }
 }
@@ -0,0 +1,23 @@
+use crate::c;
+use crate::util::flags;
+
+flags! {
+    /// Flags for `Store::backup_to`, combine them with `|`,
+    /// e.g. for "deterministic" file content:
+    /// `BackupFlags::EXCLUDE_TIMESTAMP | BackupFlags::EXCLUDE_SALT`
+    pub struct BackupFlags;
+    const NONE = 0;
+    /// Do not include the time the backup file was made
+    const EXCLUDE_TIMESTAMP = c::OBXBackupFlags_ExcludeTimestamp;
+    /// Do not include a random salt
+    const EXCLUDE_SALT = c::OBXBackupFlags_ExcludeSalt;
+}
+
+flags! {
+    /// Flags for `Opt::restore_from_backup`
+    pub struct BackupRestoreFlags;
+    /// Only restore into a database without data
+    const NONE = 0;
+    /// Replace all existing data with the content of the backup file
+    const OVERWRITE_EXISTING_DATA = c::OBXBackupRestoreFlags_OverwriteExistingData;
+}
@@ -1,28 +1,51 @@
 #![allow(dead_code)]
-use std::ptr;
-use std::rc::Rc;
+use std::sync::Arc;
 
 use crate::c::{self, *};
+use crate::r#async::{Async, AsyncBox};
+use crate::datetime::{DateTime, DateTimeNano};
 use crate::error;
 
-use crate::cursor::Cursor;
+use crate::cursor::{make_object, Cursor, CursorIter};
 use crate::query::builder::Builder;
 use crate::query::condition::Condition;
 use crate::query::Query;
 use crate::traits::{EntityFactoryExt, OBBlanket};
-use crate::util::{MutConstVoidPtr, NOT_FOUND_404};
+use crate::util::{BytesArray, IdArray, NOT_FOUND_404};
 use flatbuffers::FlatBufferBuilder;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PutMode {
+    /// Insert a new object or update an existing one
+    Put,
+    /// Fails with ErrorKind::IdAlreadyExists if the id is taken
+    Insert,
+    /// Fails with ErrorKind::IdNotFound if the id does not exist
+    Update,
+}
+
+impl PutMode {
+    pub(crate) fn to_obx(self) -> OBXPutMode {
+        match self {
+            PutMode::Put => OBXPutMode_PUT,
+            PutMode::Insert => OBXPutMode_INSERT,
+            PutMode::Update => OBXPutMode_UPDATE,
+        }
+    }
+}
+
+/// The oldest and the newest (id, time) of a time series box, see `Box::time_series_min_max`
+pub type TimeSeriesMinMax<D> = Option<((obx_id, D), (obx_id, D))>;
+
 // This Box type will confuse a lot of rust users of std::boxed::Box
 pub struct Box<'a, T: OBBlanket> {
-    pub(crate) helper: Rc<dyn EntityFactoryExt<T>>,
+    pub(crate) helper: Arc<dyn EntityFactoryExt<T>>,
     pub(crate) obx_box: *mut OBX_box,
     builder: FlatBufferBuilder<'a>,
-    // pub(crate) async_: std::boxed::Box<Async>, // TODO
 }
 
 impl<T: OBBlanket> Box<'_, T> {
-    pub(crate) fn new(store: *mut OBX_store, helper: Rc<dyn EntityFactoryExt<T>>) -> Self {
+    pub(crate) fn new(store: *mut OBX_store, helper: Arc<dyn EntityFactoryExt<T>>) -> Self {
         unsafe {
             let obx_box = c::obx_box(store, helper.get_entity_id());
 
@@ -34,6 +57,21 @@ impl<T: OBBlanket> Box<'_, T> {
         }
     }
 
+    /// Enqueue writes to the shared async queue of this box
+    pub fn async_box(&self) -> error::Result<AsyncBox<'_, T>> {
+        Async::from_box(self.obx_box).map(|a| AsyncBox::new(self.obx_box, self.helper.clone(), a))
+    }
+
+    /// Enqueue writes to a dedicated async queue, closed on drop.
+    /// Enqueueing waits up to the given timeout when the queue is full.
+    pub fn async_box_with_timeout(
+        &self,
+        enqueue_timeout_millis: u64,
+    ) -> error::Result<AsyncBox<'_, T>> {
+        Async::from_box_with_timeout(self.obx_box, enqueue_timeout_millis)
+            .map(|a| AsyncBox::new(self.obx_box, self.helper.clone(), a))
+    }
+
     // This should only be exposed between threads, channels, etc.
     pub(crate) fn get_store(&self) -> *mut OBX_store {
         unsafe { obx_box_store(self.obx_box) }
@@ -47,26 +85,40 @@ impl<T: OBBlanket> Box<'_, T> {
         )
     }
 
-    pub fn contains_many(&mut self, ids: &Vec<obx_id>) -> error::Result<Vec<bool>> {
-        let mut r = Vec::<bool>::new();
-        for id in ids {
-            match self.contains(*id) {
-                Ok(v) => r.push(v),
-                Err(err) => err.as_result()?,
-            }
+    /// Whether every id is present, in a single native call
+    pub fn contains_all(&self, ids: &[obx_id]) -> error::Result<bool> {
+        if ids.is_empty() {
+            return Ok(true);
         }
-        Ok(r)
+        let id_array = IdArray::new(ids)?;
+        let mut contains = false;
+        c::get_result(
+            unsafe { obx_box_contains_many(self.obx_box, id_array.as_ptr(), &mut contains) },
+            contains,
+        )
     }
 
-    /*
-      // TODO extension trait for Vec<u32?/OBX_id> -> OBX_id_array, see util.rs
-      // TODO alternative: run contains one by one
-      pub fn contains_many_id_array(&mut self, ids: *const OBX_id_array) -> bool {
-          let mut contains = false;
-          c::call(unsafe { obx_box_contains_many(self.obx_box, ids, &mut contains) });
-          contains
-      }
+    /// Whether each id is present, in a single read transaction
+    pub fn contains_many(&mut self, ids: &[obx_id]) -> error::Result<Vec<bool>> {
+        if ids.is_empty() {
+            return Ok(vec![]);
+        }
+        let id_array = IdArray::new(ids)?;
+        let _cursor = Cursor::new(false, self.get_store(), self.helper.clone())?;
+        self.present(&id_array)
+    }
 
+    /// Per id presence with one obx_box_get_many call,
+    /// runs in the transaction that is active on this thread
+    fn present(&self, id_array: &IdArray) -> error::Result<Vec<bool>> {
+        let bytes_array =
+            BytesArray::from_raw(unsafe { obx_box_get_many(self.obx_box, id_array.as_ptr()) })?;
+        Ok((0..bytes_array.len())
+            .map(|i| bytes_array.get(i).is_some())
+            .collect())
+    }
+
+    /*
       // TODO extension trait for mut_const_c_void -> slice -> Vec<u8> to be processed by flatbuffers
       pub fn get_data_ptr(
           &mut self,
@@ -78,11 +130,6 @@ impl<T: OBBlanket> Box<'_, T> {
           (data, size)
       }
 
-      // TODO extension trait for Vec<u32?/OBX_id> -> &[Entity], see util.rs
-      fn get_many_bytes_array(&self, ids: *const OBX_id_array) -> *mut OBX_bytes_array {
-          unsafe { obx_box_get_many(self.obx_box, ids) }
-      }
-
       // TODO convert OBX_bytes_array into &[Entity]
       fn get_all_bytes_array(&self) -> *mut OBX_bytes_array {
           unsafe { obx_box_get_all(self.obx_box) }
@@ -154,20 +201,6 @@ impl<T: OBBlanket> Box<'_, T> {
       ) {
         c::call(unsafe { obx_box_put_many(self.obx_box, objects, ids, mode) });
       }
-
-      pub fn put_many5_bytes_array(&mut self, objects: *const OBX_bytes_array, ids: *const obx_id, mode: OBXPutMode, fail_on_id_failure: bool) {
-        c::call(unsafe { obx_box_put_many5(self.obx_box, objects, ids, mode, fail_on_id_failure) });
-      }
-
-    // TODO size 16, align 8
-    fn remove_many_id_array(&mut self, ids: *const OBX_id_array) -> u64 {
-        let out_count: u64 = 0;
-        c::call(
-            unsafe { obx_box_remove_many(self.obx_box, ids, out_count as *mut u64) },
-            Some("box::remove_many_id_array"),
-        )
-        out_count
-    }
     */
 
     pub fn remove_with_id(&mut self, id: obx_id) -> error::Result<bool> {
@@ -177,15 +210,20 @@ impl<T: OBBlanket> Box<'_, T> {
         }
     }
 
-    pub fn remove_many(&mut self, ids: &Vec<c::obx_id>) -> error::Result<Vec<bool>> {
-        let mut r = Vec::<bool>::new();
-        for id in ids {
-            match self.remove_with_id(*id) {
-                Ok(v) => r.push(v),
-                Err(err) => err.as_result()?,
-            }
+    /// Removes all ids within a single write transaction,
+    /// false for ids that were not present
+    pub fn remove_many(&mut self, ids: &[c::obx_id]) -> error::Result<Vec<bool>> {
+        if ids.is_empty() {
+            return Ok(vec![]);
         }
-        Ok(r)
+        let id_array = IdArray::new(ids)?;
+        let mut cursor = Cursor::new(true, self.get_store(), self.helper.clone())?;
+        let present = self.present(&id_array)?;
+        c::call(unsafe {
+            obx_box_remove_many(self.obx_box, id_array.as_ptr(), std::ptr::null_mut())
+        })?;
+        cursor.get_tx()?.success()?;
+        Ok(present)
     }
 
     // TODO check if this is ACID (or go with cursor instead)
@@ -375,7 +413,8 @@ impl<T: OBBlanket> Box<'_, T> {
         Ok(())
     }
 
-    /// Add multiple targets to a ToMany relation at once.
+    /// Add multiple targets to a ToMany relation at once,
+    /// within a single write transaction.
     ///
     /// # Arguments
     /// * `relation_id` - The relation ID (from the model schema)
@@ -387,13 +426,15 @@ impl<T: OBBlanket> Box<'_, T> {
         source_id: obx_id,
         target_ids: &[obx_id],
     ) -> error::Result<()> {
+        let mut cursor = Cursor::new(true, self.get_store(), self.helper.clone())?;
         for &target_id in target_ids {
-            self.rel_put(relation_id, source_id, target_id)?;
+            cursor.rel_put(relation_id, source_id, target_id)?;
         }
-        Ok(())
+        cursor.get_tx()?.success()
     }
 
-    /// Remove multiple targets from a ToMany relation at once.
+    /// Remove multiple targets from a ToMany relation at once,
+    /// within a single write transaction.
     ///
     /// # Arguments
     /// * `relation_id` - The relation ID (from the model schema)
@@ -405,14 +446,111 @@ impl<T: OBBlanket> Box<'_, T> {
         source_id: obx_id,
         target_ids: &[obx_id],
     ) -> error::Result<()> {
+        let mut cursor = Cursor::new(true, self.get_store(), self.helper.clone())?;
         for &target_id in target_ids {
-            self.rel_remove(relation_id, source_id, target_id)?;
+            cursor.rel_remove(relation_id, source_id, target_id)?;
         }
-        Ok(())
+        cursor.get_tx()?.success()
     }
 
     // ==================== End Relation Methods ====================
 
+    // ==================== Time Series Methods ====================
+
+    /// The oldest and the newest object, as (id, time) pairs of the
+    /// `#[id_companion]` DateTime property, None when the box is empty.
+    /// Use `time_series_min_max_nanos` for a DateTimeNano companion.
+    pub fn time_series_min_max(&self) -> error::Result<TimeSeriesMinMax<DateTime>> {
+        let r = self.ts_min_max(OBXPropertyType_Date, None)?;
+        Ok(r.map(|((min_id, min), (max_id, max))| {
+            ((min_id, DateTime(min)), (max_id, DateTime(max)))
+        }))
+    }
+
+    /// Like `time_series_min_max`, limited to the objects within `begin..=end`
+    pub fn time_series_min_max_range(
+        &self,
+        begin: DateTime,
+        end: DateTime,
+    ) -> error::Result<TimeSeriesMinMax<DateTime>> {
+        let r = self.ts_min_max(OBXPropertyType_Date, Some((begin.0, end.0)))?;
+        Ok(r.map(|((min_id, min), (max_id, max))| {
+            ((min_id, DateTime(min)), (max_id, DateTime(max)))
+        }))
+    }
+
+    /// Like `time_series_min_max`, for a DateTimeNano `#[id_companion]`
+    pub fn time_series_min_max_nanos(&self) -> error::Result<TimeSeriesMinMax<DateTimeNano>> {
+        let r = self.ts_min_max(OBXPropertyType_DateNano, None)?;
+        Ok(r.map(|((min_id, min), (max_id, max))| {
+            ((min_id, DateTimeNano(min)), (max_id, DateTimeNano(max)))
+        }))
+    }
+
+    /// Like `time_series_min_max_range`, for a DateTimeNano `#[id_companion]`
+    pub fn time_series_min_max_range_nanos(
+        &self,
+        begin: DateTimeNano,
+        end: DateTimeNano,
+    ) -> error::Result<TimeSeriesMinMax<DateTimeNano>> {
+        let r = self.ts_min_max(OBXPropertyType_DateNano, Some((begin.0, end.0)))?;
+        Ok(r.map(|((min_id, min), (max_id, max))| {
+            ((min_id, DateTimeNano(min)), (max_id, DateTimeNano(max)))
+        }))
+    }
+
+    // the raw values are in the unit of the companion property
+    fn ts_min_max(
+        &self,
+        companion_type: OBXPropertyType,
+        range: Option<(i64, i64)>,
+    ) -> error::Result<TimeSeriesMinMax<i64>> {
+        match self.helper.get_id_companion_type() {
+            Some(t) if t == companion_type => {}
+            Some(_) => {
+                return Err(error::Error::new_local(
+                    "Error: box: the #[id_companion] has another time unit, use the DateTime or DateTimeNano variant",
+                ));
+            }
+            None => {
+                return Err(error::Error::new_local(
+                    "Error: box: time series lookups need an #[id_companion] property",
+                ));
+            }
+        }
+        let mut min_id: obx_id = 0;
+        let mut min_value: i64 = 0;
+        let mut max_id: obx_id = 0;
+        let mut max_value: i64 = 0;
+        let code = unsafe {
+            match range {
+                None => obx_box_ts_min_max(
+                    self.obx_box,
+                    &mut min_id,
+                    &mut min_value,
+                    &mut max_id,
+                    &mut max_value,
+                ),
+                Some((begin, end)) => obx_box_ts_min_max_range(
+                    self.obx_box,
+                    begin,
+                    end,
+                    &mut min_id,
+                    &mut min_value,
+                    &mut max_id,
+                    &mut max_value,
+                ),
+            }
+        };
+        c::call(code)?;
+        if code == NOT_FOUND_404 {
+            return Ok(None);
+        }
+        Ok(Some(((min_id, min_value), (max_id, max_value))))
+    }
+
+    // ==================== End Time Series Methods ====================
+
     /// A box has a longer lifetime than a cursor,
     /// and the only thing keeping this method here
     /// is the FB Builder.
@@ -424,43 +562,101 @@ impl<T: OBBlanket> Box<'_, T> {
         cursor: &mut Cursor<T>,
         object: &mut T,
     ) -> error::Result<c::obx_id> {
-        let old_id = object.get_id();
-        let is_object_new = old_id == 0;
-        let new_id = cursor.id_for_put(old_id);
-        object.set_id(new_id);
-
-        object.flatten(&mut self.builder);
-        let data = Vec::from(self.builder.finished_data());
-
-        if is_object_new {
-            cursor.put_new(new_id, &data)?;
-        } else {
-            cursor.put(new_id, &data)?;
-        }
-
-        Ok(new_id)
+        cursor.put_entity(&mut self.builder, object)
     }
 
     pub fn put(&mut self, object: &mut T) -> error::Result<c::obx_id> {
+        self.put_with_mode(object, PutMode::Put)
+    }
+
+    pub fn insert(&mut self, object: &mut T) -> error::Result<c::obx_id> {
+        self.put_with_mode(object, PutMode::Insert)
+    }
+
+    pub fn update(&mut self, object: &mut T) -> error::Result<c::obx_id> {
+        self.put_with_mode(object, PutMode::Update)
+    }
+
+    pub fn put_with_mode(&mut self, object: &mut T, mode: PutMode) -> error::Result<c::obx_id> {
         let mut cursor = Cursor::new(true, self.get_store(), self.helper.clone())?;
 
-        let new_id = self.put_entity_in_ob(&mut cursor, object);
-        cursor.get_tx().success()?;
+        let new_id = cursor.put_entity_with_mode(&mut self.builder, object, mode)?;
+        cursor.get_tx()?.success()?;
 
-        new_id
+        Ok(new_id)
     }
 
     pub fn put_many(&mut self, objects: Vec<&mut T>) -> error::Result<Vec<c::obx_id>> {
-        let mut cursor = Cursor::new(true, self.get_store(), self.helper.clone())?;
+        self.put_many_with_mode(objects, PutMode::Put)
+    }
 
-        let mut vec_out = Vec::<c::obx_id>::new();
+    pub fn insert_many(&mut self, objects: Vec<&mut T>) -> error::Result<Vec<c::obx_id>> {
+        self.put_many_with_mode(objects, PutMode::Insert)
+    }
 
-        for o in objects {
-            vec_out.push(self.put_entity_in_ob(&mut cursor, o)?);
+    pub fn update_many(&mut self, objects: Vec<&mut T>) -> error::Result<Vec<c::obx_id>> {
+        self.put_many_with_mode(objects, PutMode::Update)
+    }
+
+    /// All or nothing, in a single native call.
+    /// New objects get their ids assigned up front.
+    pub fn put_many_with_mode(
+        &mut self,
+        mut objects: Vec<&mut T>,
+        mode: PutMode,
+    ) -> error::Result<Vec<c::obx_id>> {
+        if objects.is_empty() {
+            return Ok(vec![]);
+        }
+        let new_count = objects.iter().filter(|o| o.get_id() == 0).count();
+        if new_count > 0 && mode == PutMode::Update {
+            return Err(error::Error::new_local_kind(
+                error::ErrorKind::IdNotFound,
+                "Error: box: can't update an object without an id",
+            ));
+        }
+
+        let mut next_id: obx_id = 0;
+        if new_count > 0 {
+            c::call(unsafe { obx_box_ids_for_put(self.obx_box, new_count as u64, &mut next_id) })?;
         }
 
-        cursor.get_tx().success()?;
-        Ok(vec_out)
+        let mut ids = Vec::<c::obx_id>::with_capacity(objects.len());
+        let mut data = Vec::<Vec<u8>>::with_capacity(objects.len());
+        let mut is_new = Vec::<bool>::with_capacity(objects.len());
+        for o in objects.iter_mut() {
+            let new = o.get_id() == 0;
+            if new {
+                o.set_id(next_id);
+                next_id += 1;
+            }
+            is_new.push(new);
+            ids.push(o.get_id());
+            o.flatten(&mut self.builder);
+            data.push(Vec::from(self.builder.finished_data()));
+        }
+
+        let bytes_array = BytesArray::new(data)?;
+        let r = c::call(unsafe {
+            obx_box_put_many5(
+                self.obx_box,
+                bytes_array.as_ptr(),
+                ids.as_ptr(),
+                mode.to_obx(),
+                true,
+            )
+        });
+
+        if r.is_err() {
+            // nothing was stored, don't hand out the reserved ids
+            for (o, new) in objects.iter_mut().zip(is_new) {
+                if new {
+                    o.set_id(0);
+                }
+            }
+        }
+
+        r.map(|_| ids)
     }
 
     /// For testing purposes
@@ -474,39 +670,35 @@ impl<T: OBBlanket> Box<'_, T> {
         cursor.get_entity(id)
     }
 
+    /// Fetches all ids in a single native call within a read transaction, None for missing ids
     pub fn get_many(&self, ids: &[c::obx_id]) -> error::Result<Vec<Option<T>>> {
-        let mut cursor = Cursor::new(false, self.get_store(), self.helper.clone())?;
-
-        let mut r = Vec::<Option<T>>::new();
-
-        for id in ids {
-            r.push(cursor.get_entity(*id)?);
+        if ids.is_empty() {
+            return Ok(vec![]);
         }
-        Ok(r)
+        let id_array = IdArray::new(ids)?;
+        // the returned data points into the store, only valid within the read transaction
+        let _cursor = Cursor::new(false, self.get_store(), self.helper.clone())?;
+        let bytes_array =
+            BytesArray::from_raw(unsafe { obx_box_get_many(self.obx_box, id_array.as_ptr()) })?;
+
+        let helper = self.helper.as_ref();
+        Ok((0..bytes_array.len())
+            .map(|i| bytes_array.get(i).map(|data| make_object(helper, data)))
+            .collect())
     }
 
     /// Returns all stored objects in this Box
     pub fn get_all(&self) -> error::Result<Vec<T>> {
         let mut cursor = Cursor::new(false, self.get_store(), self.helper.clone())?;
 
-        let data_ptr_ptr: *mut *mut u8 = &mut ptr::null_mut();
-
-        let size_ptr: *mut usize = &mut 0;
-
-        let mut vec: Vec<T> = Vec::new();
-
-        let mut code = cursor.first(data_ptr_ptr as MutConstVoidPtr, size_ptr)?;
-
-        // c::OBX_NOT_FOUND was a C #define that became a u32
-        // which is incompatible with obx_err === i32
-        while code != NOT_FOUND_404 {
-            unsafe {
-                vec.push(cursor.from_raw_parts_to_object(data_ptr_ptr, size_ptr));
-            }
-            code = cursor.next(data_ptr_ptr as MutConstVoidPtr, size_ptr)?;
-        }
+        cursor.get_all_entities()
+    }
 
-        Ok(vec)
+    /// Lazily iterates over all stored objects in this Box,
+    /// inside a single read transaction that lives as long as the iterator.
+    pub fn iter(&self) -> error::Result<impl Iterator<Item = error::Result<T>> + '_> {
+        let cursor = Cursor::new(false, self.get_store(), self.helper.clone())?;
+        Ok(CursorIter::new(cursor))
     }
 
     // TODO
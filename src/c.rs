@@ -8,7 +8,7 @@
 #![allow(unused_assignments)]
 include!("./c_bindings.rs");
 
-use crate::error::Error;
+use crate::error::{Error, ErrorKind};
 use std::{error, ffi, fmt, ptr};
 
 /**
@@ -20,7 +20,7 @@ use std::{error, ffi, fmt, ptr};
 
 pub enum NativeErrorKind {
     NullPtr,
-    Other, // TODO maybe implement types based on OBX_ERROR_*
+    Other, // see error::ErrorKind for the OBX_ERROR_* based kinds
 }
 
 #[derive(Debug, Clone)]
@@ -52,6 +52,18 @@ impl NativeError {
             }
         }
     }
+
+    pub fn code(&self) -> i32 {
+        self.code
+    }
+
+    pub fn secondary(&self) -> i32 {
+        self.secondary
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
 }
 
 impl fmt::Display for NativeError {
@@ -100,6 +112,15 @@ pub fn call(result: obx_err) -> Result<(), Error> {
     }
 }
 
+/// The error for a failed status passed to a callback. Callbacks run on native threads,
+/// the last error may not be set there, so unlike `call` this only relies on the code.
+pub(crate) fn status_error(status: obx_err, context: &str) -> Error {
+    Error::new_local_kind(
+        ErrorKind::from_code(status),
+        &format!("Error: {context}: failed with status {status}"),
+    )
+}
+
 /// Validates the obx_err returned from a native call, and return a Result with some Ok(value).
 /// This should be used with the '?' operator
 pub fn get_result<T>(result: obx_err, returnValue: T) -> Result<T, Error> {
@@ -118,6 +139,16 @@ pub fn get_result_from_ptr<S, T>(ptr: *mut S, returnValue: T) -> Result<T, Error
     }
 }
 
+/// Validates the obx_id returned from a native call, where 0 signals an error.
+/// This should be used with the '?' operator
+pub fn get_result_from_id(id: obx_id) -> Result<obx_id, Error> {
+    if id == 0 {
+        Err(Error::new_native(NativeError::_new(NativeErrorKind::Other)))
+    } else {
+        Ok(id)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -286,4 +317,52 @@ pub const OBXOrderFlags_UNSIGNED: OBXOrderFlags = 4;
 pub const OBXOrderFlags_NULLS_LAST: OBXOrderFlags = 8;
 
 /// null values should be treated equal to zero (scalars only).
-pub const OBXOrderFlags_NULLS_ZERO: OBXOrderFlags = 16;
\ No newline at end of file
+pub const OBXOrderFlags_NULLS_ZERO: OBXOrderFlags = 16;
+pub type OBXBackupFlags = ::std::os::raw::c_uint;
+
+/// Do not include a timestamp in the backup file (time when the backup file is generated).
+pub const OBXBackupFlags_ExcludeTimestamp: OBXBackupFlags = 1;
+
+/// Do not include a random salt in the backup file.
+pub const OBXBackupFlags_ExcludeSalt: OBXBackupFlags = 2;
+
+pub type OBXBackupRestoreFlags = ::std::os::raw::c_uint;
+
+/// Overwrite any existing database with the content of the backup file.
+pub const OBXBackupRestoreFlags_OverwriteExistingData: OBXBackupRestoreFlags = 1;
+
+pub type OBXTreeOptionFlags = ::std::os::raw::c_uint;
+
+/// Debug logs are always disabled for this tree regardless of the store's debug flags.
+pub const OBXTreeOptionFlags_DebugLogsDisable: OBXTreeOptionFlags = 1;
+
+/// Debug logs are always enabled for this tree regardless of the store's debug flags.
+pub const OBXTreeOptionFlags_DebugLogsEnable: OBXTreeOptionFlags = 2;
+
+/// A path can not address a branch and a leaf at the same time.
+pub const OBXTreeOptionFlags_EnforceUniquePath: OBXTreeOptionFlags = 4;
+
+/// Multiple nodes of the same type at the same path (e.g. with Sync) are not an error, the first one is picked.
+pub const OBXTreeOptionFlags_AllowNonUniqueNodes: OBXTreeOptionFlags = 8;
+
+/// Non-unique nodes are detected, to consolidate them manually.
+pub const OBXTreeOptionFlags_DetectNonUniqueNodes: OBXTreeOptionFlags = 16;
+
+/// Non-unique nodes are consolidated automatically, e.g. on put/remove. Implies DetectNonUniqueNodes.
+pub const OBXTreeOptionFlags_AutoConsolidateNonUniqueNodes: OBXTreeOptionFlags = 32;
+
+pub type OBXValidateOnOpenPagesFlags = ::std::os::raw::c_uint;
+
+/// Enable validation of leaf pages (by default only branch pages are validated).
+pub const OBXValidateOnOpenPagesFlags_VisitLeafPages: OBXValidateOnOpenPagesFlags = 1;
+
+/// Flags for key/value validation when opening the store; there are none besides "None" yet.
+pub type OBXValidateOnOpenKvFlags = ::std::os::raw::c_uint;
+
+pub type OBXWalFlags = ::std::os::raw::c_uint;
+
+/// Enable Wal
+pub const OBXWalFlags_EnableWal: OBXWalFlags = 1;
+
+/// Does not wait for the disk to acknowledge; faster but not ACID compliant (not generally recommended).
+pub const OBXWalFlags_NoSyncFile: OBXWalFlags = 2;
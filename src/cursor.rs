@@ -1,20 +1,33 @@
 #![allow(dead_code)]
-use std::{ptr, rc::Rc, slice::from_raw_parts};
+use std::{ptr, slice::from_raw_parts, sync::Arc};
+
+use flatbuffers::FlatBufferBuilder;
 
 use crate::{
     c::{self, *},
     error,
-    traits::EntityFactoryExt,
+    r#box::PutMode,
+    traits::{EntityFactoryExt, OBBlanket},
     txn::Tx,
-    util::{MutConstVoidPtr, ToCVoid, NOT_FOUND_404},
+    util::{IdArray, MutConstVoidPtr, ToCVoid, NOT_FOUND_404},
 };
 
+/// Inflates a flatbuffer, as stored by ObjectBox, into an object
+pub(crate) fn make_object<T>(helper: &dyn EntityFactoryExt<T>, data_slice: &[u8]) -> T {
+    let first_offset: usize = data_slice[0].into();
+
+    // TODO check speed improvement if table is recycled
+    let mut table = unsafe { flatbuffers::Table::new(data_slice, first_offset) };
+    helper.make(&mut table)
+}
+
 // The best article ever on ffi
 // https://blog.guillaume-gomez.fr/articles/2021-07-29+Interacting+with+data+from+FFI+in+Rust
 pub(crate) struct Cursor<T> {
-    helper: Rc<dyn EntityFactoryExt<T>>,
+    helper: Arc<dyn EntityFactoryExt<T>>,
     pub(crate) obx_cursor: *mut c::OBX_cursor,
-    tx: Tx,
+    // None when the cursor borrows a transaction owned elsewhere, e.g. by a Transaction
+    tx: Option<Tx>,
 }
 
 impl<T> Drop for Cursor<T> {
@@ -34,7 +47,7 @@ impl<T> Cursor<T> {
     pub(crate) fn new(
         is_mut: bool,
         store: *mut c::OBX_store,
-        helper: Rc<dyn EntityFactoryExt<T>>,
+        helper: Arc<dyn EntityFactoryExt<T>>,
     ) -> error::Result<Self> {
         let entity_id = helper.get_entity_id();
         let tx = if is_mut {
@@ -45,12 +58,31 @@ impl<T> Cursor<T> {
         c::new_mut(unsafe { c::obx_cursor(tx.obx_txn, entity_id) }).map(|obx_cursor| Cursor {
             helper,
             obx_cursor,
-            tx,
+            tx: Some(tx),
         })
     }
 
-    pub(crate) fn get_tx(&mut self) -> &mut Tx {
-        &mut self.tx
+    /// The cursor does not own the transaction, the caller must
+    /// keep the transaction open for as long as the cursor lives.
+    pub(crate) fn from_txn(
+        obx_txn: *mut c::OBX_txn,
+        helper: Arc<dyn EntityFactoryExt<T>>,
+    ) -> error::Result<Self> {
+        let entity_id = helper.get_entity_id();
+        c::new_mut(unsafe { c::obx_cursor(obx_txn, entity_id) }).map(|obx_cursor| Cursor {
+            helper,
+            obx_cursor,
+            tx: None,
+        })
+    }
+
+    pub(crate) fn get_tx(&mut self) -> error::Result<&mut Tx> {
+        match self.tx.as_mut() {
+            Some(tx) => Ok(tx),
+            None => Err(error::Error::new_local(
+                "Error: cursor: the transaction is owned by a Transaction",
+            )),
+        }
     }
 
     pub(crate) unsafe fn from_raw_parts_to_object(
@@ -59,11 +91,7 @@ impl<T> Cursor<T> {
         size_ptr: *mut usize,
     ) -> T {
         let data_slice = from_raw_parts(*data_ptr_ptr, *size_ptr);
-        let first_offset: usize = data_slice[0].into();
-
-        // TODO check speed improvement if table is recycled
-        let mut table = flatbuffers::Table::new(data_slice, first_offset);
-        self.helper.make(&mut table)
+        make_object(self.helper.as_ref(), data_slice)
     }
 
     pub(crate) fn get_entity(&mut self, id: c::obx_id) -> error::Result<Option<T>> {
@@ -87,6 +115,28 @@ impl<T> Cursor<T> {
         }
     }
 
+    /// Returns every object reachable by this cursor, in id order.
+    pub(crate) fn get_all_entities(&mut self) -> error::Result<Vec<T>> {
+        let data_ptr_ptr: *mut *mut u8 = &mut ptr::null_mut();
+
+        let size_ptr: *mut usize = &mut 0;
+
+        let mut vec: Vec<T> = Vec::new();
+
+        let mut code = self.first(data_ptr_ptr as MutConstVoidPtr, size_ptr)?;
+
+        // c::OBX_NOT_FOUND was a C #define that became a u32
+        // which is incompatible with obx_err === i32
+        while code != NOT_FOUND_404 {
+            unsafe {
+                vec.push(self.from_raw_parts_to_object(data_ptr_ptr, size_ptr));
+            }
+            code = self.next(data_ptr_ptr as MutConstVoidPtr, size_ptr)?;
+        }
+
+        Ok(vec)
+    }
+
     pub(crate) fn id_for_put(&self, id_or_zero: obx_id) -> obx_id {
         unsafe { obx_cursor_id_for_put(self.obx_cursor, id_or_zero) }
     }
@@ -96,38 +146,30 @@ impl<T> Cursor<T> {
             c::obx_cursor_put(self.obx_cursor, id, data.to_const_c_void(), data.len())
         })
     }
-    /*
-      fn put4(
-          &mut self,
-          id: obx_id,
-          data: &Vec<u8>, // bad idea
-          mode: OBXPutMode,
-      ) {
-        c::call(unsafe { obx_cursor_put4(self.obx_cursor, id, data.to_const_c_void(), data.len(), mode) }).err();
-      }
-    */
+    pub(crate) fn put4(
+        &mut self,
+        id: obx_id,
+        data: &Vec<u8>,
+        mode: OBXPutMode,
+    ) -> error::Result<()> {
+        c::call(unsafe {
+            obx_cursor_put4(
+                self.obx_cursor,
+                id,
+                data.to_const_c_void(),
+                data.len(),
+                mode,
+            )
+        })
+    }
+
     pub(crate) fn put_new(&mut self, id: obx_id, data: &Vec<u8>) -> error::Result<()> {
         c::call(unsafe {
             obx_cursor_put_new(self.obx_cursor, id, data.to_const_c_void(), data.len())
         })
     }
-    /*
-      fn insert(
-          &mut self,
-          id: obx_id,
-          data: &Vec<u8>, // bad idea
-      ) {
-          c::call(unsafe { obx_cursor_insert(self.obx_cursor, id, data.to_const_c_void(), data.len()) }).err()
-      }
-
-      fn update(
-          &mut self,
-          id: obx_id,
-          data: &Vec<u8>, // bad idea
-      ) {
-          c::call(unsafe { obx_cursor_update(self.obx_cursor, id, data.to_const_c_void(), data.len()) }).err()
-      }
 
+    /*
       fn put_object(
           &self,
           data: *mut ::std::os::raw::c_void,
@@ -198,7 +240,7 @@ impl<T> Cursor<T> {
         }
     }
 
-    fn remove(&mut self, id: obx_id) -> error::Result<()> {
+    pub(crate) fn remove(&mut self, id: obx_id) -> error::Result<()> {
         c::call(unsafe { obx_cursor_remove(self.obx_cursor, id) })
     }
 
@@ -227,7 +269,7 @@ impl<T> Cursor<T> {
 
     // TODO Determine: do we need a Tx for is_empty? Or just use the box
     // TODO test endianness
-    fn is_empty(&mut self) -> error::Result<bool> {
+    pub(crate) fn is_empty(&mut self) -> error::Result<bool> {
         unsafe {
             let out_is_empty: *mut bool = &mut false; // coerce
             c::call(obx_cursor_is_empty(
@@ -256,7 +298,7 @@ impl<T> Cursor<T> {
         unsafe { obx_cursor_backlink_ids(self.obx_cursor, entity_id, property_id, id) }
     }
 
-    fn rel_put(
+    pub(crate) fn rel_put(
         &mut self,
         relation_id: obx_schema_id,
         source_id: obx_id,
@@ -265,7 +307,7 @@ impl<T> Cursor<T> {
         c::call(unsafe { obx_cursor_rel_put(self.obx_cursor, relation_id, source_id, target_id) })
     }
 
-    fn rel_remove(
+    pub(crate) fn rel_remove(
         &mut self,
         relation_id: obx_schema_id,
         source_id: obx_id,
@@ -276,8 +318,13 @@ impl<T> Cursor<T> {
         })
     }
 
-    fn rel_ids(&self, relation_id: obx_schema_id, source_id: obx_id) -> *mut OBX_id_array {
-        unsafe { obx_cursor_rel_ids(self.obx_cursor, relation_id, source_id) }
+    pub(crate) fn rel_get_ids(
+        &self,
+        relation_id: obx_schema_id,
+        source_id: obx_id,
+    ) -> error::Result<Vec<obx_id>> {
+        IdArray::from_raw(unsafe { obx_cursor_rel_ids(self.obx_cursor, relation_id, source_id) })
+            .map(|ids| ids.to_vec())
     }
 
     /*
@@ -300,3 +347,137 @@ impl<T> Cursor<T> {
     }
     */
 }
+
+impl<T: OBBlanket> Cursor<T> {
+    /// Assigns a new id to the object when it has none,
+    /// flattens it with the (recycled) builder, then stores it.
+    pub(crate) fn put_entity(
+        &mut self,
+        builder: &mut FlatBufferBuilder,
+        object: &mut T,
+    ) -> error::Result<obx_id> {
+        self.put_entity_with_mode(builder, object, PutMode::Put)
+    }
+
+    pub(crate) fn put_entity_with_mode(
+        &mut self,
+        builder: &mut FlatBufferBuilder,
+        object: &mut T,
+        mode: PutMode,
+    ) -> error::Result<obx_id> {
+        let old_id = object.get_id();
+        let is_object_new = old_id == 0;
+
+        if is_object_new && mode == PutMode::Update {
+            return Err(error::Error::new_local_kind(
+                error::ErrorKind::IdNotFound,
+                "Error: cursor: can't update an object without an id",
+            ));
+        }
+
+        let new_id = self.id_for_put(old_id);
+        object.set_id(new_id);
+
+        object.flatten(builder);
+        let data = Vec::from(builder.finished_data());
+
+        let r = match mode {
+            PutMode::Put if is_object_new => self.put_new(new_id, &data),
+            PutMode::Put => self.put(new_id, &data),
+            PutMode::Insert | PutMode::Update => self.put4(new_id, &data, mode.to_obx()),
+        };
+
+        if r.is_err() && is_object_new {
+            // nothing was stored, don't hand out the reserved id
+            object.set_id(old_id);
+        }
+
+        r.map(|_| new_id)
+    }
+}
+
+/// Walks the cursor lazily with obx_cursor_first / obx_cursor_next,
+/// only one object is inflated at a time.
+pub(crate) struct CursorIter<T> {
+    cursor: Cursor<T>,
+    started: bool,
+    done: bool,
+}
+
+impl<T> CursorIter<T> {
+    pub(crate) fn new(cursor: Cursor<T>) -> Self {
+        CursorIter {
+            cursor,
+            started: false,
+            done: false,
+        }
+    }
+}
+
+impl<T> Iterator for CursorIter<T> {
+    type Item = error::Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let mut data_ptr: *mut u8 = ptr::null_mut();
+        let mut size: usize = 0;
+        let data_ptr_ptr: *mut *mut u8 = &mut data_ptr;
+        let size_ptr: *mut usize = &mut size;
+
+        let result = if self.started {
+            self.cursor.next(data_ptr_ptr as MutConstVoidPtr, size_ptr)
+        } else {
+            self.started = true;
+            self.cursor.first(data_ptr_ptr as MutConstVoidPtr, size_ptr)
+        };
+
+        match result {
+            Ok(NOT_FOUND_404) => {
+                self.done = true;
+                None
+            }
+            Ok(_) => Some(Ok(unsafe {
+                self.cursor.from_raw_parts_to_object(data_ptr_ptr, size_ptr)
+            })),
+            Err(err) => {
+                self.done = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
+
+/// Fetches the objects behind the ids lazily, one at a time,
+/// in the same read transaction the ids were found in.
+pub(crate) struct IdsIter<T> {
+    cursor: Cursor<T>,
+    ids: std::vec::IntoIter<obx_id>,
+}
+
+impl<T> IdsIter<T> {
+    pub(crate) fn new(cursor: Cursor<T>, ids: Vec<obx_id>) -> Self {
+        IdsIter {
+            cursor,
+            ids: ids.into_iter(),
+        }
+    }
+}
+
+impl<T> Iterator for IdsIter<T> {
+    type Item = error::Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let id = self.ids.next()?;
+            match self.cursor.get_entity(id) {
+                Ok(Some(object)) => return Some(Ok(object)),
+                // removed in the meantime, skip
+                Ok(None) => continue,
+                Err(err) => return Some(Err(err)),
+            }
+        }
+    }
+}
@@ -5,7 +5,7 @@ use std::{error, fmt, result};
 #[derive(Clone)]
 enum Repr {
     Native(c::NativeError),
-    Local(String),
+    Local(ErrorKind, String),
 }
 
 #[derive(Clone)]
@@ -13,6 +13,108 @@ pub struct Error {
     repr: Repr,
 }
 
+/// Maps the native OBX_ERROR_* codes, see also generator/src/ob_consts.rs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// Raised on the rust side, without a more specific kind
+    Local,
+    NoSuccess,
+    Timeout,
+    IllegalState,
+    IllegalArgument,
+    Allocation,
+    NumericOverflow,
+    FeatureNotAvailable,
+    ShuttingDown,
+    Io,
+    BackupFileInvalid,
+    NoErrorInfo,
+    General,
+    Unknown,
+    DbFull,
+    MaxReadersExceeded,
+    StoreMustShutdown,
+    MaxDataSizeExceeded,
+    DbGeneral,
+    StorageGeneral,
+    UniqueViolated,
+    NonUniqueResult,
+    PropertyTypeMismatch,
+    IdAlreadyExists,
+    IdNotFound,
+    TimeSeries,
+    ConstraintViolated,
+    StdIllegalArgument,
+    StdOutOfRange,
+    StdLength,
+    StdBadAlloc,
+    StdRange,
+    StdOverflow,
+    StdOther,
+    SchemaMismatch,
+    FileCorrupt,
+    FilePagesCorrupt,
+    SchemaObjectNotFound,
+    TreeModelInvalid,
+    TreeValueTypeMismatch,
+    TreePathNonUnique,
+    TreePathIllegal,
+    TreeOther,
+    /// A native code this crate does not know (yet)
+    Other(i32),
+}
+
+impl ErrorKind {
+    pub fn from_code(code: i32) -> ErrorKind {
+        use ErrorKind::*;
+        match code as u32 {
+            c::OBX_NO_SUCCESS => NoSuccess,
+            c::OBX_TIMEOUT => Timeout,
+            c::OBX_ERROR_ILLEGAL_STATE => IllegalState,
+            c::OBX_ERROR_ILLEGAL_ARGUMENT => IllegalArgument,
+            c::OBX_ERROR_ALLOCATION => Allocation,
+            c::OBX_ERROR_NUMERIC_OVERFLOW => NumericOverflow,
+            c::OBX_ERROR_FEATURE_NOT_AVAILABLE => FeatureNotAvailable,
+            c::OBX_ERROR_SHUTTING_DOWN => ShuttingDown,
+            c::OBX_ERROR_IO => Io,
+            c::OBX_ERROR_BACKUP_FILE_INVALID => BackupFileInvalid,
+            c::OBX_ERROR_NO_ERROR_INFO => NoErrorInfo,
+            c::OBX_ERROR_GENERAL => General,
+            c::OBX_ERROR_UNKNOWN => Unknown,
+            c::OBX_ERROR_DB_FULL => DbFull,
+            c::OBX_ERROR_MAX_READERS_EXCEEDED => MaxReadersExceeded,
+            c::OBX_ERROR_STORE_MUST_SHUTDOWN => StoreMustShutdown,
+            c::OBX_ERROR_MAX_DATA_SIZE_EXCEEDED => MaxDataSizeExceeded,
+            c::OBX_ERROR_DB_GENERAL => DbGeneral,
+            c::OBX_ERROR_STORAGE_GENERAL => StorageGeneral,
+            c::OBX_ERROR_UNIQUE_VIOLATED => UniqueViolated,
+            c::OBX_ERROR_NON_UNIQUE_RESULT => NonUniqueResult,
+            c::OBX_ERROR_PROPERTY_TYPE_MISMATCH => PropertyTypeMismatch,
+            c::OBX_ERROR_ID_ALREADY_EXISTS => IdAlreadyExists,
+            c::OBX_ERROR_ID_NOT_FOUND => IdNotFound,
+            c::OBX_ERROR_TIME_SERIES => TimeSeries,
+            c::OBX_ERROR_CONSTRAINT_VIOLATED => ConstraintViolated,
+            c::OBX_ERROR_STD_ILLEGAL_ARGUMENT => StdIllegalArgument,
+            c::OBX_ERROR_STD_OUT_OF_RANGE => StdOutOfRange,
+            c::OBX_ERROR_STD_LENGTH => StdLength,
+            c::OBX_ERROR_STD_BAD_ALLOC => StdBadAlloc,
+            c::OBX_ERROR_STD_RANGE => StdRange,
+            c::OBX_ERROR_STD_OVERFLOW => StdOverflow,
+            c::OBX_ERROR_STD_OTHER => StdOther,
+            c::OBX_ERROR_SCHEMA => SchemaMismatch,
+            c::OBX_ERROR_FILE_CORRUPT => FileCorrupt,
+            c::OBX_ERROR_FILE_PAGES_CORRUPT => FilePagesCorrupt,
+            c::OBX_ERROR_SCHEMA_OBJECT_NOT_FOUND => SchemaObjectNotFound,
+            c::OBX_ERROR_TREE_MODEL_INVALID => TreeModelInvalid,
+            c::OBX_ERROR_TREE_VALUE_TYPE_MISMATCH => TreeValueTypeMismatch,
+            c::OBX_ERROR_TREE_PATH_NON_UNIQUE => TreePathNonUnique,
+            c::OBX_ERROR_TREE_PATH_ILLEGAL => TreePathIllegal,
+            c::OBX_ERROR_TREE_OTHER => TreeOther,
+            _ => Other(code),
+        }
+    }
+}
+
 impl Error {
     pub fn new_native(native_error: c::NativeError) -> Error {
         Error {
@@ -21,21 +123,49 @@ impl Error {
     }
 
     pub fn new_local(local_error: &str) -> Error {
+        Self::new_local_kind(ErrorKind::Local, local_error)
+    }
+
+    /// A local error that mirrors a native error kind, e.g. IdNotFound
+    pub fn new_local_kind(kind: ErrorKind, local_error: &str) -> Error {
         Error {
-            repr: Repr::Local(String::from(local_error)),
+            repr: Repr::Local(kind, String::from(local_error)),
         }
     }
 
     pub fn as_result<T>(&self) -> Result<T> {
         Err(self.clone())
     }
+
+    pub fn kind(&self) -> ErrorKind {
+        match &self.repr {
+            Repr::Native(err) => ErrorKind::from_code(err.code()),
+            Repr::Local(kind, _) => *kind,
+        }
+    }
+
+    /// The native OBX_ERROR_* code, None for local errors
+    pub fn code(&self) -> Option<i32> {
+        match &self.repr {
+            Repr::Native(err) => Some(err.code()),
+            Repr::Local(..) => None,
+        }
+    }
+
+    /// The native secondary code, None for local errors
+    pub fn secondary_code(&self) -> Option<i32> {
+        match &self.repr {
+            Repr::Native(err) => Some(err.secondary()),
+            Repr::Local(..) => None,
+        }
+    }
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
         match &self.repr {
             Repr::Native(ref err) => write!(fmt, "{}", err),
-            Repr::Local(s) => write!(fmt, "{}", s),
+            Repr::Local(_, s) => write!(fmt, "{}", s),
         }
     }
 }
@@ -44,7 +174,7 @@ impl fmt::Debug for Repr {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
         match &*self {
             Repr::Native(ref err) => fmt::Debug::fmt(&err, fmt),
-            Repr::Local(s) => fmt::Debug::fmt(&s, fmt),
+            Repr::Local(_, s) => fmt::Debug::fmt(&s, fmt),
         }
     }
 }
@@ -59,7 +189,7 @@ impl error::Error for Error {
     fn source(&self) -> Option<&(dyn error::Error + 'static)> {
         match self.repr {
             Repr::Native(ref err) => err.source(),
-            Repr::Local(_) => None,
+            Repr::Local(..) => None,
         }
     }
 }
@@ -76,5 +206,17 @@ mod tests {
     fn fail_local_error() {
         let err = Error::new_local("test");
         assert_eq!(format!("{err}"), "test");
+        assert_eq!(err.kind(), ErrorKind::Local);
+        assert_eq!(err.code(), None);
+    }
+
+    #[test]
+    fn error_kind_from_code() {
+        assert_eq!(ErrorKind::from_code(10201), ErrorKind::UniqueViolated);
+        assert_eq!(ErrorKind::from_code(10101), ErrorKind::DbFull);
+        assert_eq!(ErrorKind::from_code(10210), ErrorKind::IdAlreadyExists);
+        assert_eq!(ErrorKind::from_code(10211), ErrorKind::IdNotFound);
+        assert_eq!(ErrorKind::from_code(10501), ErrorKind::SchemaMismatch);
+        assert_eq!(ErrorKind::from_code(12345), ErrorKind::Other(12345));
     }
 }
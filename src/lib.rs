@@ -5,11 +5,17 @@ pub extern crate flatbuffers as flatbuffers;
 pub extern crate objectbox_generator as generator;
 pub extern crate objectbox_macros as macros;
 
+#[cfg(feature = "admin")]
+pub mod admin;
+pub mod r#async;
 pub mod r#box;
+pub mod backup;
 pub mod c;
 pub mod datetime;
 pub mod error;
+pub mod logging;
 pub mod model;
+pub mod observer;
 pub mod opt;
 pub mod relations;
 pub mod store;
@@ -18,10 +24,10 @@ pub mod version;
 
 pub mod query;
 pub mod traits;
+pub mod tree;
+pub mod txn;
 
-mod r#async;
 mod cursor;
-mod txn;
 
 // TODO do the prelude thing, in the generated objectbox_gen.rs
 // use objectbox::prelude::*;
@@ -0,0 +1,129 @@
+//! Native log events, see `Opt::log_callback`.
+//!
+//! With the `log` or `tracing` cargo feature, `forward_to_log` / `forward_to_tracing`
+//! route them into the application's logs, under the `objectbox::native` target.
+
+use std::ffi::{c_char, c_void};
+use std::panic::{self, AssertUnwindSafe};
+use std::slice;
+
+use crate::c::{self, OBXLogLevel};
+
+/// The log target of forwarded native log events
+pub const NATIVE_TARGET: &str = "objectbox::native";
+
+/// The level of a native log event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LogLevel {
+    Verbose,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    pub(crate) fn from_obx(level: OBXLogLevel) -> Self {
+        match level {
+            l if l >= c::OBXLogLevel_Error => LogLevel::Error,
+            l if l >= c::OBXLogLevel_Warn => LogLevel::Warn,
+            l if l >= c::OBXLogLevel_Info => LogLevel::Info,
+            l if l >= c::OBXLogLevel_Debug => LogLevel::Debug,
+            _ => LogLevel::Verbose,
+        }
+    }
+}
+
+/// Receives native log events, on whichever thread the event happened.
+/// A plain fn, because the options are consumed by the store.
+pub type LogCallback = fn(LogLevel, &str);
+
+// The user data is the LogCallback itself, nothing to free
+pub(crate) unsafe extern "C" fn log_trampoline(
+    level: OBXLogLevel,
+    message: *const c_char,
+    message_size: usize,
+    user_data: *mut c_void,
+) {
+    if user_data.is_null() {
+        return;
+    }
+    let f = std::mem::transmute::<*mut c_void, LogCallback>(user_data);
+    let message = if message.is_null() {
+        Default::default()
+    } else {
+        String::from_utf8_lossy(slice::from_raw_parts(message as *const u8, message_size))
+    };
+    let message = message.trim_end_matches('\0');
+    let r = panic::catch_unwind(AssertUnwindSafe(|| f(LogLevel::from_obx(level), message)));
+    if r.is_err() {
+        eprintln!("Error: log: callback panicked");
+    }
+}
+
+/// A `LogCallback` that emits `log` records
+#[cfg(feature = "log")]
+pub fn forward_to_log(level: LogLevel, message: &str) {
+    let level = match level {
+        LogLevel::Verbose => log::Level::Trace,
+        LogLevel::Debug => log::Level::Debug,
+        LogLevel::Info => log::Level::Info,
+        LogLevel::Warn => log::Level::Warn,
+        LogLevel::Error => log::Level::Error,
+    };
+    log::log!(target: NATIVE_TARGET, level, "{}", message);
+}
+
+/// A `LogCallback` that emits `tracing` events
+#[cfg(feature = "tracing")]
+pub fn forward_to_tracing(level: LogLevel, message: &str) {
+    // the tracing macros need the level at compile time
+    match level {
+        LogLevel::Verbose => tracing::trace!(target: NATIVE_TARGET, "{}", message),
+        LogLevel::Debug => tracing::debug!(target: NATIVE_TARGET, "{}", message),
+        LogLevel::Info => tracing::info!(target: NATIVE_TARGET, "{}", message),
+        LogLevel::Warn => tracing::warn!(target: NATIVE_TARGET, "{}", message),
+        LogLevel::Error => tracing::error!(target: NATIVE_TARGET, "{}", message),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    static RECEIVED: Mutex<Vec<(LogLevel, String)>> = Mutex::new(Vec::new());
+
+    fn record(level: LogLevel, message: &str) {
+        RECEIVED.lock().unwrap().push((level, message.to_string()));
+    }
+
+    #[test]
+    fn levels_from_obx() {
+        assert_eq!(
+            LogLevel::Verbose,
+            LogLevel::from_obx(c::OBXLogLevel_Verbose)
+        );
+        assert_eq!(LogLevel::Info, LogLevel::from_obx(c::OBXLogLevel_Info));
+        assert_eq!(LogLevel::Warn, LogLevel::from_obx(c::OBXLogLevel_Warn + 5));
+        assert_eq!(LogLevel::Error, LogLevel::from_obx(c::OBXLogLevel_Error));
+        assert!(LogLevel::Warn > LogLevel::Info);
+    }
+
+    #[test]
+    fn trampoline_calls_the_fn() {
+        let message = b"opening store\0";
+        unsafe {
+            log_trampoline(
+                c::OBXLogLevel_Warn,
+                message.as_ptr() as *const c_char,
+                message.len(),
+                record as LogCallback as *mut c_void,
+            )
+        };
+        assert_eq!(
+            vec![(LogLevel::Warn, "opening store".to_string())],
+            *RECEIVED.lock().unwrap()
+        );
+    }
+}
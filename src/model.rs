@@ -128,6 +128,89 @@ impl Model {
         self
     }
 
+    /// Set the vector dimensionality for the HNSW index of the last created property,
+    /// mandatory for HNSW indexes. The property must be a vector type, e.g. Vec<f32>.
+    pub fn property_index_hnsw_dimensions(mut self, dimensions: usize) -> Self {
+        if self.error.is_none() {
+            self.error = c::call(unsafe {
+                c::obx_model_property_index_hnsw_dimensions(self.obx_model, dimensions)
+            })
+            .err();
+        }
+        self
+    }
+
+    /// Max number of neighbors per node (aka "M") for the HNSW index, defaults to 30.
+    pub fn property_index_hnsw_neighbors_per_node(mut self, value: u32) -> Self {
+        if self.error.is_none() {
+            self.error = c::call(unsafe {
+                c::obx_model_property_index_hnsw_neighbors_per_node(self.obx_model, value)
+            })
+            .err();
+        }
+        self
+    }
+
+    /// Max number of neighbors searched while indexing (aka "efConstruction"), defaults to 100.
+    pub fn property_index_hnsw_indexing_search_count(mut self, value: u32) -> Self {
+        if self.error.is_none() {
+            self.error = c::call(unsafe {
+                c::obx_model_property_index_hnsw_indexing_search_count(self.obx_model, value)
+            })
+            .err();
+        }
+        self
+    }
+
+    /// See OBXHnswFlags.
+    pub fn property_index_hnsw_flags(mut self, flags: u32) -> Self {
+        if self.error.is_none() {
+            self.error =
+                c::call(unsafe { c::obx_model_property_index_hnsw_flags(self.obx_model, flags) })
+                    .err();
+        }
+        self
+    }
+
+    /// See OBXVectorDistanceType, defaults to Euclidean.
+    pub fn property_index_hnsw_distance_type(
+        mut self,
+        distance_type: c::OBXVectorDistanceType,
+    ) -> Self {
+        if self.error.is_none() {
+            self.error = c::call(unsafe {
+                c::obx_model_property_index_hnsw_distance_type(self.obx_model, distance_type)
+            })
+            .err();
+        }
+        self
+    }
+
+    /// Probability of adding backlinks when repairing the graph after a removal, defaults to 1.0.
+    pub fn property_index_hnsw_reparation_backlink_probability(mut self, value: f32) -> Self {
+        if self.error.is_none() {
+            self.error = c::call(unsafe {
+                c::obx_model_property_index_hnsw_reparation_backlink_probability(
+                    self.obx_model,
+                    value,
+                )
+            })
+            .err();
+        }
+        self
+    }
+
+    /// Non-binding hint of the max vector cache size in KB, defaults to 2 GB.
+    pub fn property_index_hnsw_vector_cache_hint_size_kb(mut self, value: usize) -> Self {
+        if self.error.is_none() {
+            self.error = c::call(unsafe {
+                c::obx_model_property_index_hnsw_vector_cache_hint_size_kb(self.obx_model, value)
+            })
+            .err();
+        }
+        self
+    }
+
     /// Declare a to-one relation on the last created property.
     /// No need to declare the index separately using property_index(), it's created automatically.
     pub fn property_relation(
@@ -204,6 +287,36 @@ mod tests {
         assert!(model.error.is_none());
     }
 
+    #[test]
+    fn model_builder_hnsw() {
+        let model = Model::new()
+            .entity("A", 1, 1)
+            .property(
+                "id",
+                1,
+                101,
+                c::OBXPropertyType_Long,
+                c::OBXPropertyFlags_ID,
+            )
+            .property(
+                "embedding",
+                2,
+                202,
+                c::OBXPropertyType_FloatVector,
+                c::OBXPropertyFlags_INDEXED,
+            )
+            .property_index(1, 301)
+            .property_index_hnsw_dimensions(3)
+            .property_index_hnsw_distance_type(c::OBXVectorDistanceType_Cosine)
+            .property_index_hnsw_neighbors_per_node(16)
+            .property_index_hnsw_indexing_search_count(200)
+            .last_property_id(2, 202)
+            .last_entity_id(1, 1)
+            .last_index_id(1, 301);
+
+        assert!(model.error.is_none());
+    }
+
     #[test]
     fn big_model_test() {
         let model = Model::new()
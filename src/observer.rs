@@ -0,0 +1,114 @@
+#![allow(dead_code)]
+use std::ffi::c_void;
+use std::marker::PhantomData;
+use std::panic::{self, AssertUnwindSafe};
+use std::slice;
+use std::sync::Mutex;
+
+use crate::c::{self, *};
+use crate::error;
+use crate::store::Store;
+
+pub(crate) type SingleTypeCallback = Box<dyn FnMut() + Send>;
+pub(crate) type TypesCallback = Box<dyn FnMut(&[obx_schema_id]) + Send>;
+
+// Keeps the closure at a stable heap address, passed as user_data
+enum Callback {
+    SingleType(Box<Mutex<SingleTypeCallback>>),
+    Types(Box<Mutex<TypesCallback>>),
+}
+
+/// Listens to data changes until dropped.
+/// The callbacks run on a background thread owned by ObjectBox,
+/// keep them short, or forward the work elsewhere (e.g. over a channel).
+/// Must not outlive the Store.
+pub struct Observer<'s> {
+    obx_observer: *mut OBX_observer,
+    // dropped after the native observer is closed
+    callback: Option<Callback>,
+    phantom_data: PhantomData<&'s Store>,
+}
+
+impl Drop for Observer<'_> {
+    fn drop(&mut self) {
+        if !self.obx_observer.is_null() {
+            if let Err(err) = c::call(unsafe { obx_observer_close(self.obx_observer) }) {
+                eprintln!("Error: observer: {err}");
+                // the native side may still call it (e.g. dropped inside its own callback),
+                // leak the closure rather than free it under its feet
+                std::mem::forget(self.callback.take());
+            }
+            self.obx_observer = std::ptr::null_mut();
+        }
+    }
+}
+
+unsafe extern "C" fn single_type_trampoline(user_data: *mut c_void) {
+    let callback = &*(user_data as *const Mutex<SingleTypeCallback>);
+    // unwinding into native code is undefined behavior
+    let r = panic::catch_unwind(AssertUnwindSafe(|| {
+        if let Ok(mut f) = callback.lock() {
+            f()
+        }
+    }));
+    if r.is_err() {
+        eprintln!("Error: observer: callback panicked");
+    }
+}
+
+unsafe extern "C" fn types_trampoline(
+    type_ids: *const obx_schema_id,
+    type_ids_count: usize,
+    user_data: *mut c_void,
+) {
+    let callback = &*(user_data as *const Mutex<TypesCallback>);
+    let ids = if type_ids.is_null() || type_ids_count == 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(type_ids, type_ids_count)
+    };
+    let r = panic::catch_unwind(AssertUnwindSafe(|| {
+        if let Ok(mut f) = callback.lock() {
+            f(ids)
+        }
+    }));
+    if r.is_err() {
+        eprintln!("Error: observer: callback panicked");
+    }
+}
+
+impl<'s> Observer<'s> {
+    pub(crate) fn new_single_type(
+        store: &'s Store,
+        type_id: obx_schema_id,
+        f: SingleTypeCallback,
+    ) -> error::Result<Self> {
+        let boxed = Box::new(Mutex::new(f));
+        let user_data = &*boxed as *const Mutex<SingleTypeCallback> as *mut c_void;
+        let obx_observer = c::new_mut(unsafe {
+            obx_observe_single_type(
+                store.obx_store,
+                type_id,
+                Some(single_type_trampoline),
+                user_data,
+            )
+        })?;
+        Ok(Observer {
+            obx_observer,
+            callback: Some(Callback::SingleType(boxed)),
+            phantom_data: PhantomData,
+        })
+    }
+
+    pub(crate) fn new_types(store: &'s Store, f: TypesCallback) -> error::Result<Self> {
+        let boxed = Box::new(Mutex::new(f));
+        let user_data = &*boxed as *const Mutex<TypesCallback> as *mut c_void;
+        let obx_observer =
+            c::new_mut(unsafe { obx_observe(store.obx_store, Some(types_trampoline), user_data) })?;
+        Ok(Observer {
+            obx_observer,
+            callback: Some(Callback::Types(boxed)),
+            phantom_data: PhantomData,
+        })
+    }
+}
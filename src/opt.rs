@@ -2,9 +2,13 @@
 use std::ffi::{c_uint, CStr, CString};
 use std::path::Path;
 
+use crate::backup::BackupRestoreFlags;
+use crate::logging::{log_trampoline, LogCallback};
 use crate::model::Model;
-use crate::util::{ToCVoid};
-use crate::{c::*, error};
+use crate::store::Store;
+use crate::traits::FactoryMap;
+use crate::util::{flags, ToCVoid};
+use crate::{c, c::*, error};
 
 pub struct Opt {
     pub(crate) obx_opt: *mut OBX_store_options,
@@ -51,6 +55,22 @@ impl Opt {
         call(unsafe { obx_opt_directory(self.obx_opt, c_str.as_ptr()) }).map(|_| self)
     }
 
+    /// Restores the database from a backup file (see `Store::backup_to`) when the store opens.
+    /// By default only a database without data is restored into.
+    /// Only available in the server edition of the native library.
+    pub fn restore_from_backup(
+        &self,
+        backup_file: &Path,
+        flags: BackupRestoreFlags,
+    ) -> error::Result<&Self> {
+        let c_str = CString::new(backup_file.to_str().unwrap_or(""))
+            .map_err(|e| error::Error::new_local(&format!("Invalid backup path: {}", e)))?;
+        unsafe {
+            obx_opt_backup_restore(self.obx_opt, c_str.as_ptr(), flags.bits());
+        }
+        Ok(self)
+    }
+
     pub fn max_db_size_in_kb(&self, size_in_kb: u64) -> &Self {
         unsafe {
             obx_opt_max_db_size_in_kb(self.obx_opt, size_in_kb);
@@ -100,9 +120,47 @@ impl Opt {
             .map(|_| self)
     }
 
-    pub fn validate_on_open_pages(&self, page_limit: usize, flags: u32) -> &Self {
+    /// Checks up to `page_limit` database pages when opening the store, 0 disables it
+    pub fn validate_on_open_pages(
+        &self,
+        page_limit: usize,
+        flags: ValidateOnOpenPagesFlags,
+    ) -> &Self {
+        unsafe {
+            obx_opt_validate_on_open_pages(self.obx_opt, page_limit, flags.bits());
+        }
+        self
+    }
+
+    /// Checks the key/value pairs against the internal specification when opening the store
+    pub fn validate_on_open_kv(&self, flags: ValidateOnOpenKvFlags) -> &Self {
         unsafe {
-            obx_opt_validate_on_open_pages(self.obx_opt, page_limit, flags);
+            obx_opt_validate_on_open_kv(self.obx_opt, flags.bits());
+        }
+        self
+    }
+
+    /// Enables the write-ahead log with WalFlags::ENABLE_WAL.
+    /// For now only supported by in-memory databases.
+    pub fn wal(&self, flags: WalFlags) -> &Self {
+        unsafe {
+            obx_opt_wal(self.obx_opt, flags.bits());
+        }
+        self
+    }
+
+    /// The WAL file is consolidated after a commit once it reaches this size, default 16 MB
+    pub fn wal_max_file_size_in_kb(&self, size_in_kb: u64) -> &Self {
+        unsafe {
+            obx_opt_wal_max_file_size_in_kb(self.obx_opt, size_in_kb);
+        }
+        self
+    }
+
+    /// The WAL file is consolidated when opening the store once it reaches this size, default 4 MB
+    pub fn wal_max_file_size_on_open_in_kb(&self, size_in_kb: u64) -> &Self {
+        unsafe {
+            obx_opt_wal_max_file_size_on_open_in_kb(self.obx_opt, size_in_kb);
         }
         self
     }
@@ -269,6 +327,19 @@ impl Opt {
         self
     }
 
+    /// Calls `f` for a selection of native log events (in addition to the native logging),
+    /// e.g. `logging::forward_to_log` with the `log` feature
+    pub fn log_callback(&self, f: LogCallback) -> &Self {
+        unsafe {
+            obx_opt_log_callback(
+                self.obx_opt,
+                Some(log_trampoline),
+                f as *const () as *mut std::ffi::c_void,
+            );
+        }
+        self
+    }
+
     pub fn get_directory(&self) -> &str {
         unsafe {
             let c_str = obx_opt_get_directory(self.obx_opt);
@@ -296,3 +367,159 @@ impl Opt {
         self.obx_opt
     }
 }
+
+flags! {
+    /// Flags for `Opt::wal`, combine them with `|`
+    pub struct WalFlags;
+    const NONE = 0;
+    const ENABLE_WAL = c::OBXWalFlags_EnableWal;
+    /// Does not wait for the disk to acknowledge, faster but not ACID compliant
+    const NO_SYNC_FILE = c::OBXWalFlags_NoSyncFile;
+}
+
+flags! {
+    /// Flags for `Opt::validate_on_open_pages`
+    pub struct ValidateOnOpenPagesFlags;
+    /// Only branch pages are validated
+    const NONE = 0;
+    /// Validate the leaf pages as well
+    const VISIT_LEAF_PAGES = c::OBXValidateOnOpenPagesFlags_VisitLeafPages;
+}
+
+flags! {
+    /// Flags for `Opt::validate_on_open_kv`, the native library defines none besides NONE yet
+    pub struct ValidateOnOpenKvFlags;
+    const NONE = 0;
+}
+
+/// Consuming counterpart of Opt, to chain the options into opening the store:
+/// `OptBuilder::from_model(&mut model).directory(dir).open(make_factory_map())`.
+/// Like Model, the first error is kept and returned by `build` or `open`.
+pub struct OptBuilder {
+    opt: Opt,
+    error: Option<error::Error>,
+}
+
+impl From<Opt> for OptBuilder {
+    fn from(opt: Opt) -> Self {
+        OptBuilder { opt, error: None }
+    }
+}
+
+impl Default for OptBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OptBuilder {
+    pub fn new() -> Self {
+        Opt::new().into()
+    }
+
+    pub fn from_model(model: &mut Model) -> Self {
+        match Opt::from_model(model) {
+            Ok(opt) => opt.into(),
+            Err(err) => OptBuilder {
+                opt: Opt::new(),
+                error: Some(err),
+            },
+        }
+    }
+
+    pub fn directory(mut self, dir: &Path) -> Self {
+        if self.error.is_none() {
+            self.error = self.opt.directory(dir).err();
+        }
+        self
+    }
+
+    pub fn restore_from_backup(mut self, backup_file: &Path, flags: BackupRestoreFlags) -> Self {
+        if self.error.is_none() {
+            self.error = self.opt.restore_from_backup(backup_file, flags).err();
+        }
+        self
+    }
+
+    pub fn max_db_size_in_kb(self, size_in_kb: u64) -> Self {
+        self.opt.max_db_size_in_kb(size_in_kb);
+        self
+    }
+
+    pub fn max_data_size_in_kb(self, size_in_kb: u64) -> Self {
+        self.opt.max_data_size_in_kb(size_in_kb);
+        self
+    }
+
+    pub fn file_mode(self, file_mode: u32) -> Self {
+        self.opt.file_mode(file_mode);
+        self
+    }
+
+    pub fn max_readers(self, max_readers: u32) -> Self {
+        self.opt.max_readers(max_readers);
+        self
+    }
+
+    pub fn read_only(self, value: bool) -> Self {
+        self.opt.read_only(value);
+        self
+    }
+
+    pub fn use_previous_commit(self, value: bool) -> Self {
+        self.opt.use_previous_commit(value);
+        self
+    }
+
+    pub fn debug_flags(self, flags: u32) -> Self {
+        self.opt.debug_flags(flags);
+        self
+    }
+
+    pub fn validate_on_open_pages(
+        self,
+        page_limit: usize,
+        flags: ValidateOnOpenPagesFlags,
+    ) -> Self {
+        self.opt.validate_on_open_pages(page_limit, flags);
+        self
+    }
+
+    pub fn validate_on_open_kv(self, flags: ValidateOnOpenKvFlags) -> Self {
+        self.opt.validate_on_open_kv(flags);
+        self
+    }
+
+    pub fn wal(self, flags: WalFlags) -> Self {
+        self.opt.wal(flags);
+        self
+    }
+
+    pub fn wal_max_file_size_in_kb(self, size_in_kb: u64) -> Self {
+        self.opt.wal_max_file_size_in_kb(size_in_kb);
+        self
+    }
+
+    pub fn wal_max_file_size_on_open_in_kb(self, size_in_kb: u64) -> Self {
+        self.opt.wal_max_file_size_on_open_in_kb(size_in_kb);
+        self
+    }
+
+    pub fn log_callback(self, f: LogCallback) -> Self {
+        self.opt.log_callback(f);
+        self
+    }
+
+    /// The options, or the first error, e.g. for the remaining `Opt` setters
+    pub fn build(self) -> error::Result<Opt> {
+        match self.error {
+            Some(err) => Err(err),
+            None => Ok(self.opt),
+        }
+    }
+
+    /// Opens the store with these options, see `Store::new`
+    pub fn open(self, map: FactoryMap) -> error::Result<Store> {
+        Store::new(self.build()?, map)
+    }
+}
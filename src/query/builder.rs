@@ -2,7 +2,7 @@
 use std::{
     ffi::CString,
     marker::PhantomData,
-    rc::Rc,
+    sync::Arc,
 };
 
 // TODO also error check before chaining the next call (obx_qb_cond)
@@ -17,6 +17,9 @@ use crate::{
 };
 
 use super::condition::Condition;
+use super::link::{LinkKind, LinkedCondition};
+use super::order::OrderFlags;
+use super::traits::BasicExt;
 use crate::query::Query;
 
 impl<T: OBBlanket> Drop for Builder<T> {
@@ -27,16 +30,27 @@ impl<T: OBBlanket> Drop for Builder<T> {
             }
             self.obx_query_builder = std::ptr::null_mut();
         }
+        // the query reads through the linked builders, close them only with this one
+        for qb in self.linked_builders.drain(..) {
+            if let Err(err) = c::call(unsafe { obx_qb_close(qb) }) {
+                eprintln!("Error: query builder: {err}");
+            }
+        }
     }
 }
 
 pub struct Builder<T: OBBlanket> {
     obx_store: *mut OBX_store,
-    helper: Rc<dyn EntityFactoryExt<T>>,
+    // None for linked builders, these can't be built
+    helper: Option<Arc<dyn EntityFactoryExt<T>>>,
     property_id: obx_schema_id,
     obx_query_builder: *mut OBX_query_builder,
     case_sensitive: bool,
     has_built_query: bool,
+    // first error that isn't reported through obx_qb_error_code
+    error: Option<error::Error>,
+    // builders from obx_qb_link_* / obx_qb_backlink_*, including nested ones
+    linked_builders: Vec<*mut OBX_query_builder>,
     phantom_data: PhantomData<T>,
 }
 
@@ -52,19 +66,80 @@ impl<T: OBBlanket> Builder<T> {
 
         let mut builder = Builder {
             obx_store,
-            helper: box_store.helper.clone(),
+            helper: Some(box_store.helper.clone()),
             property_id: 0,
             obx_query_builder,
             has_built_query: false,
             case_sensitive: false,
+            error: None,
+            linked_builders: Vec::new(),
             phantom_data: PhantomData,
         };
 
         condition.visit_dfs(&mut |c| builder.get_condition_integer(c));
 
+        if let Some(err) = builder.error.take() {
+            return Err(err);
+        }
         c::get_result(builder.error_code(), builder)
     }
 
+    /// Compiles a nested condition on a builder returned by obx_qb_link_* / obx_qb_backlink_*,
+    /// the linked builder and its own linked builders are handed to the parent to close
+    pub(crate) fn apply_linked(
+        obx_store: *mut OBX_store,
+        obx_query_builder: *mut OBX_query_builder,
+        condition: &mut Condition<T>,
+        linked_builders: &mut Vec<*mut OBX_query_builder>,
+    ) -> error::Result<()> {
+        let mut builder = Builder {
+            obx_store,
+            helper: None,
+            property_id: 0,
+            obx_query_builder,
+            has_built_query: false,
+            case_sensitive: false,
+            error: None,
+            linked_builders: Vec::new(),
+            phantom_data: PhantomData,
+        };
+
+        condition.visit_dfs(&mut |c| builder.get_condition_integer(c));
+
+        let r = match builder.error.take() {
+            Some(err) => Err(err),
+            None => c::call(builder.error_code()),
+        };
+        linked_builders.push(obx_query_builder);
+        linked_builders.append(&mut builder.linked_builders);
+        builder.obx_query_builder = std::ptr::null_mut();
+        r
+    }
+
+    fn link(&mut self, kind: LinkKind, nested: &mut dyn LinkedCondition) {
+        let linked_builder = unsafe {
+            match kind {
+                LinkKind::Property(property_id) => self.link_property(property_id),
+                LinkKind::BacklinkProperty(source_entity_id, source_property_id) => {
+                    self.backlink_property(source_entity_id, source_property_id)
+                }
+                LinkKind::Standalone(relation_id) => self.link_standalone(relation_id),
+                LinkKind::BacklinkStandalone(relation_id) => self.backlink_standalone(relation_id),
+            }
+        };
+        let r = c::new_mut(linked_builder)
+            .and_then(|qb| nested.apply(self.obx_store, qb, &mut self.linked_builders));
+        if let Err(err) = r {
+            self.set_error(err);
+        }
+    }
+
+    fn set_error(&mut self, err: error::Error) {
+        if self.error.is_none() {
+            self.error = Some(err);
+        }
+    }
+
     fn get_group_integer(&self, c: &Condition<T>, mut f: impl FnMut(*const i32, usize) -> c::obx_qb_cond) -> c::obx_qb_cond {
         let cs = c.collect_results();
         // do not build the (sub)tree with one result
@@ -85,6 +160,12 @@ impl<T: OBBlanket> Builder<T> {
         use super::enums::ConditionOp;
         use crate::util::to_c_string;
 
+        // the link is implied for the whole query, it doesn't produce a condition
+        if let ConditionOp::Link(kind, nested) = &mut c.op {
+            self.link(*kind, nested.as_mut());
+            return QUERY_NO_OP;
+        }
+
         unsafe {
             let result = match &c.op {
                 ConditionOp::IsNull => self.is_null(),
@@ -157,6 +238,12 @@ impl<T: OBBlanket> Builder<T> {
                     self.get_group_integer(c,  |ptr, len| self.all(ptr, len))
                 }
                 ConditionOp::Any => {
+                    if c.contains_link() {
+                        self.set_error(error::Error::new_local(
+                            "Error: query builder: a link condition can't be combined with or",
+                        ));
+                        return QUERY_NO_OP;
+                    }
                     self.get_group_integer(c,  |ptr, len| self.any(ptr, len))
                 }
                 ConditionOp::ContainsKeyValue(k, v) => {
@@ -202,6 +289,27 @@ impl<T: OBBlanket> Builder<T> {
                     let (ptr, len) = vec_i64.as_ptr_and_length_tuple::<i64>();
                     self.not_in_int64s(ptr, len)
                 }
+                ConditionOp::RelationCount(source_entity_id, source_property_id, count) => {
+                    self.relation_count_property(*source_entity_id, *source_property_id, *count)
+                }
+                ConditionOp::NearestNeighbors_f32(vector, max_result_count, dimensions) => {
+                    // the native side reads `dimensions` floats from the pointer
+                    if *dimensions == 0 {
+                        self.set_error(error::Error::new_local(
+                            "Error: query builder: nearest_neighbors needs an #[hnsw] index",
+                        ));
+                        return QUERY_NO_OP;
+                    }
+                    if vector.len() != *dimensions {
+                        self.set_error(error::Error::new_local(&format!(
+                            "Error: query builder: nearest_neighbors expects {} dimensions, got {}",
+                            dimensions,
+                            vector.len()
+                        )));
+                        return QUERY_NO_OP;
+                    }
+                    self.nearest_neighbors_f32(vector.as_ptr(), *max_result_count)
+                }
                 ConditionOp::In_String(strs) => {
                     // Convert all strings to CStrings, keeping them alive
                     let c_strings: Vec<CString> = match strs
@@ -216,15 +324,55 @@ impl<T: OBBlanket> Builder<T> {
                         c_strings.iter().map(|c| c.as_ptr()).collect();
                     self.in_strings(ptrs.as_ptr() as *const PtrConstChar, ptrs.len())
                 }
-                ConditionOp::NoOp => QUERY_NO_OP,
+                ConditionOp::Link(..) | ConditionOp::NoOp => QUERY_NO_OP,
             };
+            // obx_qb_param_alias applies to the previously created condition
+            if let Some(alias) = &c.alias {
+                if result != QUERY_NO_OP {
+                    let r = match to_c_string(alias) {
+                        Some(c_alias) => c::call(self.param_alias(c_alias.as_ptr())),
+                        None => Err(error::Error::new_local(&format!(
+                            "Error: query builder: invalid alias {alias:?}"
+                        ))),
+                    };
+                    if let Err(err) = r {
+                        self.set_error(err);
+                    }
+                }
+            }
             result
         }
     }
 
+    /// Sort the results by a property, call again to add further sort keys,
+    /// e.g. `qb.order_by(t_string.as_ref(), OrderFlags::NONE)?.order_by(t_i64.as_ref(), OrderFlags::DESCENDING)?`
+    pub fn order_by<P: BasicExt<T> + ?Sized>(
+        &mut self,
+        property: &P,
+        flags: OrderFlags,
+    ) -> error::Result<&mut Self> {
+        let entity_id = self.helper.as_ref().map(|h| h.get_entity_id());
+        if entity_id != Some(property.get_entity_id()) {
+            return Err(error::Error::new_local(
+                "Error: query builder: order_by property belongs to another entity",
+            ));
+        }
+        let code = unsafe {
+            obx_qb_order(
+                self.obx_query_builder,
+                property.get_property_id(),
+                flags.bits(),
+            )
+        };
+        c::call(code).map(|_| self)
+    }
+
     /// Why does Self::build have to be called separately?
     pub fn build(&mut self) -> error::Result<Query<T>> {
-        let query = Query::new(self.obx_store, self.helper.clone(), self.obx_query_builder)?;
+        let helper = self.helper.clone().ok_or_else(|| {
+            error::Error::new_local("Error: query builder: a linked builder can't be built")
+        })?;
+        let query = Query::new(self.obx_store, helper, self.obx_query_builder)?;
         // iff a query is built properly, then do not drop, else drop
         self.has_built_query = true;
         Ok(query)
@@ -545,6 +693,20 @@ impl<T: OBBlanket> Builder<T> {
         obx_qb_less_or_equal_bytes(self.obx_query_builder, self.property_id, value, size)
     }
 
+    // nearest neighbors Vec<f32>, requires an HNSW index
+    unsafe fn nearest_neighbors_f32(
+        &self,
+        query_vector: *const f32,
+        max_result_count: usize,
+    ) -> obx_qb_cond {
+        obx_qb_nearest_neighbors_f32(
+            self.obx_query_builder,
+            self.property_id,
+            query_vector,
+            max_result_count,
+        )
+    }
+
     // TODO create all!() macro, substitute varargs
     unsafe fn all(&self, conditions: *const obx_qb_cond, count: usize) -> obx_qb_cond {
         obx_qb_all(self.obx_query_builder, conditions, count)
@@ -559,56 +721,51 @@ impl<T: OBBlanket> Builder<T> {
         obx_qb_order(self.obx_query_builder, self.property_id, flags)
     }
 
-    // TODO support later
-    /*
     unsafe fn param_alias(&self, alias: PtrConstChar) -> obx_err {
         obx_qb_param_alias(self.obx_query_builder, alias)
     }
-    unsafe fn relation_count_property(
-        &self,
-        relation_entity_id: obx_schema_id,
-        relation_property_id: obx_schema_id,
-        relation_count: i32,
-    ) -> obx_qb_cond {
-        unsafe {
-            obx_qb_relation_count_property(
-                self.obx_query_builder,
-                relation_entity_id,
-                relation_property_id,
-                relation_count,
-            )
-        }
-    }
-    unsafe fn link_property(
-        &self,
-    ) -> *mut OBX_query_builder {
-        obx_qb_link_property(self.obx_query_builder, self.property_id)
+
+    // many-to-one
+    unsafe fn link_property(&self, property_id: obx_schema_id) -> *mut OBX_query_builder {
+        obx_qb_link_property(self.obx_query_builder, property_id)
     }
 
+    // one-to-many, a ToOne property of the source entity in reverse
     unsafe fn backlink_property(
         &self,
         source_entity_id: obx_schema_id,
         source_property_id: obx_schema_id,
     ) -> *mut OBX_query_builder {
-        unsafe {
-            obx_qb_backlink_property(self.obx_query_builder, source_entity_id, source_property_id)
-        }
+        obx_qb_backlink_property(self.obx_query_builder, source_entity_id, source_property_id)
     }
 
-    unsafe fn link_standalone(
-        &self,
-        relation_id: obx_schema_id,
-    ) -> *mut OBX_query_builder {
+    // many-to-many
+    unsafe fn link_standalone(&self, relation_id: obx_schema_id) -> *mut OBX_query_builder {
         obx_qb_link_standalone(self.obx_query_builder, relation_id)
     }
 
-    unsafe fn backlink_standalone(
-        &self,
-        relation_id: obx_schema_id,
-    ) -> *mut OBX_query_builder {
+    // many-to-many, in reverse
+    unsafe fn backlink_standalone(&self, relation_id: obx_schema_id) -> *mut OBX_query_builder {
         obx_qb_backlink_standalone(self.obx_query_builder, relation_id)
     }
 
+    // number of objects (of relation_entity_id) pointing to this one, 1:N only
+    unsafe fn relation_count_property(
+        &self,
+        relation_entity_id: obx_schema_id,
+        relation_property_id: obx_schema_id,
+        relation_count: u32,
+    ) -> obx_qb_cond {
+        obx_qb_relation_count_property(
+            self.obx_query_builder,
+            relation_entity_id,
+            relation_property_id,
+            relation_count,
+        )
+    }
+
+    // TODO support later
+    /*
     unsafe fn link_time(
         &self,
         linked_entity_id: obx_schema_id,
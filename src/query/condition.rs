@@ -21,6 +21,8 @@ pub struct Condition<Entity: OBBlanket> {
     // to the other enum values. Now we have a (directional) tree.
     pub(crate) group: Option<Vec<Self>>,
     pub(crate) result: Option<c::obx_qb_cond>,
+    // Name to set this condition's value(s) later on, via Query::set_param_alias_*
+    pub(crate) alias: Option<String>,
 }
 
 impl<Entity: OBBlanket> Condition<Entity> {
@@ -39,6 +41,7 @@ impl<Entity: OBBlanket> Condition<Entity> {
             op,
             group: Some(group),
             result: None,
+            alias: None,
         }
     }
 
@@ -49,9 +52,17 @@ impl<Entity: OBBlanket> Condition<Entity> {
             op,
             group: None,
             result: None,
+            alias: None,
         }
     }
 
+    /// Names the parameter of this condition, so its value can be
+    /// changed on the built Query, e.g. `query.set_param_alias_int("age", 42)`
+    pub fn alias(mut self, name: &str) -> Self {
+        self.alias = Some(String::from(name));
+        self
+    }
+
     pub fn or(self, that: Condition<Entity>) -> Self {
         Self::new_group(
             self.ids_and_type.clone(),
@@ -92,6 +103,15 @@ impl<Entity: OBBlanket> Condition<Entity> {
         vec
     }
 
+    /// Link conditions apply to the whole query, they can't be part of an or-group
+    pub(crate) fn contains_link(&self) -> bool {
+        matches!(self.op, ConditionOp::Link(..))
+            || self
+                .group
+                .as_ref()
+                .is_some_and(|cs| cs.iter().any(|c| c.contains_link()))
+    }
+
     pub(crate) fn visit_dfs(&mut self, f: &mut impl FnMut(&mut Self) -> c::obx_qb_cond) {
         if let Some(cs) = &mut self.group {
             for c in cs {
@@ -1,6 +1,9 @@
 #![allow(dead_code)]
 #![allow(non_camel_case_types)]
 
+use super::link::{LinkKind, LinkedCondition};
+use crate::c::obx_schema_id;
+
 pub(crate) enum ConditionOp {
     Contains(String),
     ContainsElement(String),
@@ -52,4 +55,11 @@ pub(crate) enum ConditionOp {
     NotIn_i64(Vec<i64>),
     In_String(Vec<String>),
     // NotIn_String(Vec<String>), // No op
+    // vector, max result count, the dimensions of the #[hnsw] index
+    NearestNeighbors_f32(Vec<f32>, usize, usize),
+
+    // Nested condition on a related entity
+    Link(LinkKind, Box<dyn LinkedCondition>),
+    // ToOne backlink: source entity id, source property id, exact count
+    RelationCount(obx_schema_id, obx_schema_id, u32),
 }
@@ -0,0 +1,267 @@
+#![allow(dead_code)]
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+use crate::c::{self, obx_schema_id, OBX_query_builder, OBX_store};
+use crate::error;
+use crate::traits::OBBlanket;
+
+use super::builder::Builder;
+use super::condition::Condition;
+use super::enums::ConditionOp;
+
+/// How the linked query builder is derived from its parent
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum LinkKind {
+    /// ToOne property (many-to-one)
+    Property(obx_schema_id),
+    /// ToOne property (source entity id, property id) used in reverse (one-to-many)
+    BacklinkProperty(obx_schema_id, obx_schema_id),
+    /// Standalone ToMany relation (many-to-many)
+    Standalone(obx_schema_id),
+    /// Standalone ToMany relation used in reverse
+    BacklinkStandalone(obx_schema_id),
+}
+
+/// A nested condition on another entity, compiled on the linked query builder
+pub(crate) trait LinkedCondition {
+    fn apply(
+        &mut self,
+        obx_store: *mut OBX_store,
+        obx_query_builder: *mut OBX_query_builder,
+        linked_builders: &mut Vec<*mut OBX_query_builder>,
+    ) -> error::Result<()>;
+}
+
+impl<T: OBBlanket> LinkedCondition for Condition<T> {
+    fn apply(
+        &mut self,
+        obx_store: *mut OBX_store,
+        obx_query_builder: *mut OBX_query_builder,
+        linked_builders: &mut Vec<*mut OBX_query_builder>,
+    ) -> error::Result<()> {
+        Builder::<T>::apply_linked(obx_store, obx_query_builder, self, linked_builders)
+    }
+}
+
+/// A ToOne property, many-to-one
+pub struct ToOneLink;
+/// The reverse of a ToOne property, one-to-many
+pub struct ToOneBacklink;
+/// A standalone ToMany relation, many-to-many in either direction
+pub struct ToManyLink;
+
+/// A relation from `Source` to `Target`, exposed on the generated condition factories
+/// for ToOne and ToMany fields, e.g. "orders whose customer's email ends with @acme.com":
+/// `customer.with(email.ends_with("@acme.com"))`.
+/// `Kind` is one of `ToOneLink`, `ToOneBacklink` and `ToManyLink`,
+/// it decides which conditions the link offers.
+///
+/// Link conditions always apply to the whole query, building a query that combines
+/// them with `or` fails.
+pub struct Link<Source: OBBlanket, Target: OBBlanket, Kind> {
+    source_entity_id: obx_schema_id,
+    target_entity_id: obx_schema_id,
+    kind: LinkKind,
+    phantom_data: PhantomData<(Source, Target, Kind)>,
+}
+
+impl<Source: OBBlanket, Target: OBBlanket, Kind> Link<Source, Target, Kind> {
+    fn new(
+        source_entity_id: obx_schema_id,
+        target_entity_id: obx_schema_id,
+        kind: LinkKind,
+    ) -> Self {
+        Link {
+            source_entity_id,
+            target_entity_id,
+            kind,
+            phantom_data: PhantomData,
+        }
+    }
+
+    /// The id of a standalone ToMany relation, e.g. for `Box::rel_put`
+    pub fn relation_id(&self) -> Option<obx_schema_id> {
+        match self.kind {
+            LinkKind::Standalone(relation_id) | LinkKind::BacklinkStandalone(relation_id) => {
+                Some(relation_id)
+            }
+            _ => None,
+        }
+    }
+
+    fn property_id(&self) -> obx_schema_id {
+        match self.kind {
+            LinkKind::Property(property_id) | LinkKind::BacklinkProperty(_, property_id) => {
+                property_id
+            }
+            LinkKind::Standalone(_) | LinkKind::BacklinkStandalone(_) => 0,
+        }
+    }
+
+    fn reverse<ReverseKind>(&self) -> Link<Target, Source, ReverseKind> {
+        let kind = match self.kind {
+            LinkKind::Property(property_id) => {
+                LinkKind::BacklinkProperty(self.source_entity_id, property_id)
+            }
+            LinkKind::BacklinkProperty(_, property_id) => LinkKind::Property(property_id),
+            LinkKind::Standalone(relation_id) => LinkKind::BacklinkStandalone(relation_id),
+            LinkKind::BacklinkStandalone(relation_id) => LinkKind::Standalone(relation_id),
+        };
+        Link::new(self.target_entity_id, self.source_entity_id, kind)
+    }
+
+    /// Matches the `Source` objects that have at least one
+    /// related `Target` object matching the condition
+    pub fn with(&self, condition: Condition<Target>) -> Condition<Source>
+    where
+        Target: 'static,
+    {
+        Condition::new(
+            Rc::new((
+                self.source_entity_id,
+                self.property_id(),
+                c::OBXPropertyType_Relation,
+            )),
+            ConditionOp::Link(self.kind, std::boxed::Box::new(condition)),
+        )
+    }
+}
+
+impl<Source: OBBlanket, Target: OBBlanket> Link<Source, Target, ToOneLink> {
+    /// ToOne field, `property_id` is the relation property of `Source`
+    pub fn to_one(
+        source_entity_id: obx_schema_id,
+        property_id: obx_schema_id,
+        target_entity_id: obx_schema_id,
+    ) -> Self {
+        Self::new(
+            source_entity_id,
+            target_entity_id,
+            LinkKind::Property(property_id),
+        )
+    }
+
+    /// The same relation from the other side, e.g. the orders of a customer
+    pub fn backlink(&self) -> Link<Target, Source, ToOneBacklink> {
+        self.reverse()
+    }
+}
+
+impl<Source: OBBlanket, Target: OBBlanket> Link<Source, Target, ToOneBacklink> {
+    /// The same relation from the other side, the ToOne it was derived from
+    pub fn backlink(&self) -> Link<Target, Source, ToOneLink> {
+        self.reverse()
+    }
+
+    /// Matches the `Source` objects with exactly `count` related objects,
+    /// e.g. customers without orders: `customer.backlink().relation_count(0)`.
+    /// The count is an exact match, "at least 3" can't be expressed,
+    /// combine several counts with `or` instead.
+    /// ObjectBox supports this only for the backlink of a ToOne (1:N).
+    pub fn relation_count(&self, count: u32) -> Condition<Source> {
+        let (entity_id, property_id) = match self.kind {
+            LinkKind::BacklinkProperty(entity_id, property_id) => (entity_id, property_id),
+            _ => unreachable!("a ToOneBacklink is created from a ToOne property"),
+        };
+        Condition::new(
+            Rc::new((
+                self.source_entity_id,
+                property_id,
+                c::OBXPropertyType_Relation,
+            )),
+            ConditionOp::RelationCount(entity_id, property_id, count),
+        )
+    }
+}
+
+impl<Source: OBBlanket, Target: OBBlanket> Link<Source, Target, ToManyLink> {
+    /// Standalone ToMany field
+    pub fn to_many(
+        source_entity_id: obx_schema_id,
+        relation_id: obx_schema_id,
+        target_entity_id: obx_schema_id,
+    ) -> Self {
+        Self::new(
+            source_entity_id,
+            target_entity_id,
+            LinkKind::Standalone(relation_id),
+        )
+    }
+
+    /// The same relation from the other side, e.g. the students of a teacher
+    pub fn backlink(&self) -> Link<Target, Source, ToManyLink> {
+        self.reverse()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use flatbuffers::FlatBufferBuilder;
+
+    use super::*;
+
+    use crate::{
+        c,
+        traits::{FBOBBridge, IdExt},
+    };
+
+    macro_rules! test_entity {
+        ($name:ident) => {
+            struct $name {
+                id: c::obx_id,
+            }
+
+            impl FBOBBridge for $name {
+                fn flatten(&self, _: &mut FlatBufferBuilder<'_>) {}
+            }
+
+            impl IdExt for $name {
+                fn get_id(&self) -> c::obx_id {
+                    self.id
+                }
+                fn set_id(&mut self, id: c::obx_id) {
+                    self.id = id;
+                }
+            }
+        };
+    }
+
+    test_entity!(Order);
+    test_entity!(Customer);
+
+    #[test]
+    fn link_backlink_roundtrip() {
+        let to_one = Link::<Order, Customer, ToOneLink>::to_one(1, 4, 2);
+        let back = to_one.backlink();
+        assert_eq!(LinkKind::BacklinkProperty(1, 4), back.kind);
+        assert_eq!((2, 1), (back.source_entity_id, back.target_entity_id));
+        assert_eq!(LinkKind::Property(4), back.backlink().kind);
+
+        assert_eq!(None, to_one.relation_id());
+
+        let to_many = Link::<Order, Customer, ToManyLink>::to_many(1, 9, 2);
+        assert_eq!(Some(9), to_many.backlink().relation_id());
+        assert_eq!(LinkKind::BacklinkStandalone(9), to_many.backlink().kind);
+        assert_eq!(LinkKind::Standalone(9), to_many.backlink().backlink().kind);
+    }
+
+    #[test]
+    fn link_with_wraps_condition() {
+        let it = Rc::new((2, 3, c::OBXPropertyType_String));
+        let nested = Condition::<Customer>::new(it, ConditionOp::EndsWith("@acme.com".to_string()));
+        let c = Link::<Order, Customer, ToOneLink>::to_one(1, 4, 2).with(nested);
+        assert_eq!(1, c.get_entity_id());
+        assert_eq!(4, c.get_property_id());
+        assert!(matches!(c.op, ConditionOp::Link(LinkKind::Property(4), _)));
+    }
+
+    #[test]
+    fn link_relation_count() {
+        let c = Link::<Order, Customer, ToOneLink>::to_one(1, 4, 2)
+            .backlink()
+            .relation_count(3);
+        assert_eq!(2, c.get_entity_id());
+        assert!(matches!(c.op, ConditionOp::RelationCount(1, 4, 3)));
+    }
+}
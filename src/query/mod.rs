@@ -2,7 +2,9 @@
 pub(crate) mod builder;
 pub mod condition;
 pub(crate) mod enums;
-mod query;
+pub mod link;
+pub mod property;
+pub mod order;
 pub mod traits;
 
 include!("./query.rs");
@@ -0,0 +1,20 @@
+use crate::c;
+use crate::util::flags;
+
+flags! {
+    /// Sort flags for `Builder::order_by`, combine them with `|`,
+    /// e.g. `OrderFlags::DESCENDING | OrderFlags::NULLS_LAST`
+    pub struct OrderFlags;
+    /// Ascending, case-insensitive for strings, nulls first
+    const NONE = 0;
+    /// Reverse the order from ascending (default) to descending
+    const DESCENDING = c::OBXOrderFlags_DESCENDING;
+    /// Sort upper case letters (e.g. "Z") before lower case letters (e.g. "a")
+    const CASE_SENSITIVE = c::OBXOrderFlags_CASE_SENSITIVE;
+    /// Treat signed integers as unsigned, e.g. -1 sorts after 1
+    const UNSIGNED = c::OBXOrderFlags_UNSIGNED;
+    /// Null values are put last, instead of first
+    const NULLS_LAST = c::OBXOrderFlags_NULLS_LAST;
+    /// Null values are treated as zero (scalars only)
+    const NULLS_ZERO = c::OBXOrderFlags_NULLS_ZERO;
+}
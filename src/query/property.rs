@@ -0,0 +1,216 @@
+#![allow(dead_code)]
+use std::ffi::CStr;
+use std::marker::PhantomData;
+use std::ptr;
+use std::slice;
+
+use crate::c::{self, *};
+use crate::error;
+use crate::traits::OBBlanket;
+use crate::util::{to_c_string, PtrConstChar};
+
+use super::traits::BasicExt;
+use super::Query;
+
+/// Aggregates and value lookups on a single property,
+/// over the objects matching the Query it was made from.
+/// The Query must outlive the PropertyQuery.
+pub struct PropertyQuery<'q, T: OBBlanket> {
+    obx_query_prop: *mut OBX_query_prop,
+    phantom_data: PhantomData<&'q Query<T>>,
+}
+
+impl<T: OBBlanket> Drop for PropertyQuery<'_, T> {
+    fn drop(&mut self) {
+        if !self.obx_query_prop.is_null() {
+            if let Err(err) = c::call(unsafe { obx_query_prop_close(self.obx_query_prop) }) {
+                eprintln!("Error: property query: {err}");
+            }
+            self.obx_query_prop = ptr::null_mut();
+        }
+    }
+}
+
+/// Copies the items of a native array, before it is freed
+unsafe fn copy_items<V: Copy>(items: *const V, count: usize) -> Vec<V> {
+    if count == 0 || items.is_null() {
+        return Vec::new();
+    }
+    slice::from_raw_parts(items, count).to_vec()
+}
+
+impl<'q, T: OBBlanket> PropertyQuery<'q, T> {
+    pub(crate) fn new<P: BasicExt<T> + ?Sized>(
+        query: &'q Query<T>,
+        property: &P,
+    ) -> error::Result<Self> {
+        let obx_query_prop = c::new_mut(unsafe {
+            obx_query_prop(query.obx_query, property.get_property_id())
+        })?;
+        Ok(PropertyQuery {
+            obx_query_prop,
+            phantom_data: PhantomData,
+        })
+    }
+
+    /// Only consider unique values, for strings this is case-insensitive
+    pub fn distinct(&self, distinct: bool) -> error::Result<&Self> {
+        c::call(unsafe { obx_query_prop_distinct(self.obx_query_prop, distinct) }).map(|_| self)
+    }
+
+    /// Only for string properties
+    pub fn distinct_case(&self, distinct: bool, case_sensitive: bool) -> error::Result<&Self> {
+        c::call(unsafe {
+            obx_query_prop_distinct_case(self.obx_query_prop, distinct, case_sensitive)
+        })
+        .map(|_| self)
+    }
+
+    /// Counts the non-null values, respects distinct
+    pub fn count(&self) -> error::Result<u64> {
+        let mut count = 0;
+        c::call(unsafe { obx_query_prop_count(self.obx_query_prop, &mut count) }).map(|_| count)
+    }
+
+    // Aggregates, None when there were no (non-null) values to aggregate
+
+    pub fn avg(&self) -> error::Result<Option<f64>> {
+        let mut value = 0.0;
+        let mut count = 0;
+        c::call(unsafe { obx_query_prop_avg(self.obx_query_prop, &mut value, &mut count) })
+            .map(|_| if count == 0 { None } else { Some(value) })
+    }
+
+    pub fn avg_int(&self) -> error::Result<Option<i64>> {
+        let mut value = 0;
+        let mut count = 0;
+        c::call(unsafe { obx_query_prop_avg_int(self.obx_query_prop, &mut value, &mut count) })
+            .map(|_| if count == 0 { None } else { Some(value) })
+    }
+
+    pub fn min(&self) -> error::Result<Option<f64>> {
+        let mut value = 0.0;
+        let mut count = 0;
+        c::call(unsafe { obx_query_prop_min(self.obx_query_prop, &mut value, &mut count) })
+            .map(|_| if count == 0 { None } else { Some(value) })
+    }
+
+    pub fn max(&self) -> error::Result<Option<f64>> {
+        let mut value = 0.0;
+        let mut count = 0;
+        c::call(unsafe { obx_query_prop_max(self.obx_query_prop, &mut value, &mut count) })
+            .map(|_| if count == 0 { None } else { Some(value) })
+    }
+
+    pub fn min_int(&self) -> error::Result<Option<i64>> {
+        let mut value = 0;
+        let mut count = 0;
+        c::call(unsafe { obx_query_prop_min_int(self.obx_query_prop, &mut value, &mut count) })
+            .map(|_| if count == 0 { None } else { Some(value) })
+    }
+
+    pub fn max_int(&self) -> error::Result<Option<i64>> {
+        let mut value = 0;
+        let mut count = 0;
+        c::call(unsafe { obx_query_prop_max_int(self.obx_query_prop, &mut value, &mut count) })
+            .map(|_| if count == 0 { None } else { Some(value) })
+    }
+
+    /// Zero when there are no values
+    pub fn sum(&self) -> error::Result<f64> {
+        let mut value = 0.0;
+        let mut count = 0;
+        c::call(unsafe { obx_query_prop_sum(self.obx_query_prop, &mut value, &mut count) })
+            .map(|_| value)
+    }
+
+    /// Zero when there are no values, fails on overflow
+    pub fn sum_int(&self) -> error::Result<i64> {
+        let mut value = 0;
+        let mut count = 0;
+        c::call(unsafe { obx_query_prop_sum_int(self.obx_query_prop, &mut value, &mut count) })
+            .map(|_| value)
+    }
+
+    // Value lookups, null values are skipped unless a replacement is given
+
+    pub fn find_strings(&self, value_if_null: Option<&str>) -> error::Result<Vec<String>> {
+        let c_null = match value_if_null {
+            Some(s) => Some(to_c_string(s).ok_or_else(|| {
+                error::Error::new_local("Error: property query: invalid value_if_null")
+            })?),
+            None => None,
+        };
+        let null_ptr = c_null.as_ref().map_or(ptr::null(), |s| s.as_ptr());
+        unsafe {
+            let array = c::new_mut(obx_query_prop_find_strings(self.obx_query_prop, null_ptr))?;
+            let items = copy_items((*array).items as *const PtrConstChar, (*array).count);
+            let vec = items
+                .into_iter()
+                .map(|p| CStr::from_ptr(p).to_string_lossy().into_owned())
+                .collect();
+            obx_string_array_free(array);
+            Ok(vec)
+        }
+    }
+
+    pub fn find_int64s(&self, value_if_null: Option<i64>) -> error::Result<Vec<i64>> {
+        let null_ptr = value_if_null.as_ref().map_or(ptr::null(), |v| v as *const i64);
+        unsafe {
+            let array = c::new_mut(obx_query_prop_find_int64s(self.obx_query_prop, null_ptr))?;
+            let vec = copy_items((*array).items, (*array).count);
+            obx_int64_array_free(array);
+            Ok(vec)
+        }
+    }
+
+    pub fn find_int32s(&self, value_if_null: Option<i32>) -> error::Result<Vec<i32>> {
+        let null_ptr = value_if_null.as_ref().map_or(ptr::null(), |v| v as *const i32);
+        unsafe {
+            let array = c::new_mut(obx_query_prop_find_int32s(self.obx_query_prop, null_ptr))?;
+            let vec = copy_items((*array).items, (*array).count);
+            obx_int32_array_free(array);
+            Ok(vec)
+        }
+    }
+
+    pub fn find_int16s(&self, value_if_null: Option<i16>) -> error::Result<Vec<i16>> {
+        let null_ptr = value_if_null.as_ref().map_or(ptr::null(), |v| v as *const i16);
+        unsafe {
+            let array = c::new_mut(obx_query_prop_find_int16s(self.obx_query_prop, null_ptr))?;
+            let vec = copy_items((*array).items, (*array).count);
+            obx_int16_array_free(array);
+            Ok(vec)
+        }
+    }
+
+    pub fn find_int8s(&self, value_if_null: Option<i8>) -> error::Result<Vec<i8>> {
+        let null_ptr = value_if_null.as_ref().map_or(ptr::null(), |v| v as *const i8);
+        unsafe {
+            let array = c::new_mut(obx_query_prop_find_int8s(self.obx_query_prop, null_ptr))?;
+            let vec = copy_items((*array).items, (*array).count);
+            obx_int8_array_free(array);
+            Ok(vec)
+        }
+    }
+
+    pub fn find_doubles(&self, value_if_null: Option<f64>) -> error::Result<Vec<f64>> {
+        let null_ptr = value_if_null.as_ref().map_or(ptr::null(), |v| v as *const f64);
+        unsafe {
+            let array = c::new_mut(obx_query_prop_find_doubles(self.obx_query_prop, null_ptr))?;
+            let vec = copy_items((*array).items, (*array).count);
+            obx_double_array_free(array);
+            Ok(vec)
+        }
+    }
+
+    pub fn find_floats(&self, value_if_null: Option<f32>) -> error::Result<Vec<f32>> {
+        let null_ptr = value_if_null.as_ref().map_or(ptr::null(), |v| v as *const f32);
+        unsafe {
+            let array = c::new_mut(obx_query_prop_find_floats(self.obx_query_prop, null_ptr))?;
+            let vec = copy_items((*array).items, (*array).count);
+            obx_float_array_free(array);
+            Ok(vec)
+        }
+    }
+}
@@ -1,14 +1,54 @@
 use crate::c;
 use crate::c::*;
-use crate::cursor::Cursor;
+use crate::cursor::{make_object, Cursor, IdsIter};
 use crate::error;
+use crate::r#box::PutMode;
+use crate::query::traits::BasicExt;
 use crate::traits::EntityFactoryExt;
 use crate::traits::OBBlanket;
-use crate::util::test_fn_ptr_on_char_ptr;
+use crate::txn::Tx;
+use crate::util::{
+    test_fn_ptr_on_char_ptr, to_c_string, ConstVoidPtr, MutConstVoidPtr, PtrConstChar,
+    NOT_FOUND_404,
+};
 use core::slice;
+use flatbuffers::FlatBufferBuilder;
+use std::any::Any;
+use std::ffi::{c_void, CString};
 use std::marker::PhantomData;
+use std::panic::{self, AssertUnwindSafe};
 use std::ptr;
-use std::rc::Rc;
+use std::sync::Arc;
+
+struct VisitState<'a, T> {
+    helper: &'a dyn EntityFactoryExt<T>,
+    f: &'a mut dyn FnMut(T),
+    // re-raised once the native call has returned
+    panic: Option<Box<dyn Any + Send>>,
+}
+
+unsafe extern "C" fn visit_trampoline<T>(
+    data: *const c_void,
+    size: usize,
+    user_data: *mut c_void,
+) -> bool {
+    let state = &mut *(user_data as *mut VisitState<T>);
+    if data.is_null() {
+        return true;
+    }
+    let data_slice = slice::from_raw_parts(data as *const u8, size);
+    // unwinding into native code is undefined behavior
+    let r = panic::catch_unwind(AssertUnwindSafe(|| {
+        (state.f)(make_object(state.helper, data_slice))
+    }));
+    match r {
+        Ok(_) => true,
+        Err(payload) => {
+            state.panic = Some(payload);
+            false
+        }
+    }
+}
 
 // TODO pass generic type from box, via fn
 impl<T: OBBlanket> Drop for Query<T> {
@@ -26,14 +66,14 @@ impl<T: OBBlanket> Drop for Query<T> {
 pub struct Query<T: OBBlanket> {
     obx_query: *mut OBX_query,
     obx_store: *mut OBX_store,
-    pub(crate) helper: Rc<dyn EntityFactoryExt<T>>,
+    pub(crate) helper: Arc<dyn EntityFactoryExt<T>>,
     phantom_data: PhantomData<T>,
 }
 
 impl<T: OBBlanket> Query<T> {
     pub(crate) fn new(
         obx_store: *mut OBX_store,
-        helper: Rc<dyn EntityFactoryExt<T>>,
+        helper: Arc<dyn EntityFactoryExt<T>>,
         builder: *mut OBX_query_builder,
     ) -> error::Result<Self> {
         unsafe {
@@ -102,23 +142,34 @@ impl<T: OBBlanket> Query<T> {
     //     obx_query_find(self.obx_query)
     // }
 
-    // WONTFIX: don't implement unless anyone asks for it
-    // pub(crate) unsafe fn find_first(
-    //     &mut self,
-    //     data: MutConstVoidPtr,
-    //     size: *mut usize,
-    // ) -> obx_err {
-    //     obx_query_find_first(self.obx_query, data, size)
-    // }
+    /// The first matching object, without loading the others.
+    /// Respects the order, but ignores offset.
+    pub fn find_first(&self) -> error::Result<Option<T>> {
+        self.find_single(|data, size| unsafe { obx_query_find_first(self.obx_query, data, size) })
+    }
 
-    // WONTFIX: don't implement unless anyone asks for it
-    // pub(crate) unsafe fn find_unique(
-    //     &mut self,
-    //     data: MutConstVoidPtr,
-    //     size: *mut usize,
-    // ) -> obx_err {
-    //     obx_query_find_unique(self.obx_query, data, size)
-    // }
+    /// The only matching object, fails with `ErrorKind::NonUniqueResult`
+    /// if more than one object matches. Ignores offset and limit.
+    pub fn find_unique(&self) -> error::Result<Option<T>> {
+        self.find_single(|data, size| unsafe { obx_query_find_unique(self.obx_query, data, size) })
+    }
+
+    fn find_single(
+        &self,
+        f: impl FnOnce(MutConstVoidPtr, *mut usize) -> obx_err,
+    ) -> error::Result<Option<T>> {
+        // the returned data is only valid while the transaction is open
+        let _tx = Tx::new(self.obx_store)?;
+        let mut data: ConstVoidPtr = ptr::null();
+        let mut size: usize = 0;
+        let code = f(&mut data, &mut size);
+        c::call(code)?;
+        if code == NOT_FOUND_404 || data.is_null() {
+            return Ok(None);
+        }
+        let data_slice = unsafe { slice::from_raw_parts(data as *const u8, size) };
+        Ok(Some(make_object(self.helper.as_ref(), data_slice)))
+    }
 
     // elect the cursor version
     // pub(crate) unsafe fn visit(
@@ -144,6 +195,15 @@ impl<T: OBBlanket> Query<T> {
     //     obx_query_remove(self.obx_query, out_count)
     // }
 
+    /// Aggregate or collect the values of a single property,
+    /// e.g. `query.property(t_i64.as_ref())?.sum_int()?`
+    pub fn property<P: BasicExt<T> + ?Sized>(
+        &self,
+        property: &P,
+    ) -> error::Result<crate::query::property::PropertyQuery<'_, T>> {
+        crate::query::property::PropertyQuery::new(self, property)
+    }
+
     /// For testing and debugging
     /// A function pointer is passed here, to prevent dealing with lifetime issues.
     pub fn describe(&self, fn_ptr: fn(String) -> bool) -> bool {
@@ -162,18 +222,60 @@ impl<T: OBBlanket> Query<T> {
         }
     }
 
-    /*
-        // TODO pass a closure fn to this in the pub fn impl
-        // TODO translate to iter trait?
-        unsafe fn cursor_visit(
-            &mut self,
-            cursor: &mut OBX_cursor,
-            visitor: obx_data_visitor, // typedef bool obx_data_visitor(const void *data, size_t size, void *user_data)
-            user_data: *mut ::std::os::raw::c_void,
-        ) -> obx_err {
-            obx_query_cursor_visit(self.obx_query, cursor, visitor, user_data)
+    unsafe fn cursor_visit(
+        &self,
+        cursor: &mut OBX_cursor,
+        visitor: obx_data_visitor, // typedef bool obx_data_visitor(const void *data, size_t size, void *user_data)
+        user_data: *mut ::std::os::raw::c_void,
+    ) -> obx_err {
+        obx_query_cursor_visit(self.obx_query, cursor, visitor, user_data)
+    }
+
+    /// Calls `f` with each matching object, in a single read transaction,
+    /// without collecting the results first. Respects order, offset and limit.
+    pub fn for_each(&self, mut f: impl FnMut(T)) -> error::Result<()> {
+        let cursor = Cursor::new(false, self.obx_store, self.helper.clone())?;
+        let mut state = VisitState {
+            helper: self.helper.as_ref(),
+            f: &mut f,
+            panic: None,
+        };
+        let code = unsafe {
+            self.cursor_visit(
+                &mut *cursor.obx_cursor,
+                Some(visit_trampoline::<T>),
+                &mut state as *mut VisitState<T> as *mut c_void,
+            )
+        };
+        if let Some(payload) = state.panic.take() {
+            // the read transaction is closed while unwinding
+            panic::resume_unwind(payload);
+        }
+        c::call(code)
+    }
+
+    /// Calls `f` with each matching object, the objects for which `f` returns true
+    /// are written back, all in a single write transaction. Only the matching ids
+    /// are collected up front, the objects are read one at a time.
+    /// Returns the number of updated objects; nothing is written if an error occurs.
+    pub fn update_each(&self, mut f: impl FnMut(&mut T) -> bool) -> error::Result<u64> {
+        let mut cursor = Cursor::new(true, self.obx_store, self.helper.clone())?;
+        let ids = self.find_ids_with_cursor(&mut cursor)?;
+        let mut builder = FlatBufferBuilder::new();
+        let mut count = 0;
+        for id in ids {
+            if let Some(mut object) = cursor.get_entity(id)? {
+                if f(&mut object) {
+                    cursor.put_entity_with_mode(&mut builder, &mut object, PutMode::Update)?;
+                    count += 1;
+                }
+            }
         }
+        cursor.get_tx()?.success()?;
+        Ok(count)
+    }
 
+    /*
         unsafe fn cursor_find(&self, cursor: &mut OBX_cursor) -> *mut OBX_bytes_array {
             obx_query_cursor_find(self.obx_query, cursor)
         }
@@ -203,8 +305,12 @@ impl<T: OBBlanket> Query<T> {
 
     // TODO write test
     pub fn find_ids(&self) -> error::Result<Vec<c::obx_id>> {
+        let mut cursor = Cursor::new(false, self.obx_store, self.helper.clone())?;
+        self.find_ids_with_cursor(&mut cursor)
+    }
+
+    fn find_ids_with_cursor(&self, cursor: &mut Cursor<T>) -> error::Result<Vec<c::obx_id>> {
         unsafe {
-            let cursor = Cursor::new(false, self.obx_store, self.helper.clone())?;
             let c_id_array = self.cursor_find_ids(&mut *cursor.obx_cursor);
             if c_id_array.is_null() {
                 return Err(error::Error::new_local("find_ids: null pointer returned from query"));
@@ -222,6 +328,59 @@ impl<T: OBBlanket> Query<T> {
         }
     }
 
+    /// Iterates over the query results, loading the objects lazily.
+    /// The matching ids are still collected up front, so memory grows
+    /// with the number of results (8 bytes per id); the objects themselves
+    /// are read one at a time, in a single read transaction
+    /// that lives as long as the iterator.
+    /// obx_query_cursor_visit can only push objects into a callback,
+    /// use `for_each` to walk the results without collecting anything.
+    pub fn iter(&self) -> error::Result<impl Iterator<Item = error::Result<T>> + '_> {
+        let mut cursor = Cursor::new(false, self.obx_store, self.helper.clone())?;
+        let ids = self.find_ids_with_cursor(&mut cursor)?;
+        Ok(IdsIter::new(cursor, ids))
+    }
+
+    /// Objects with their query score, e.g. the distance for a nearest_neighbors
+    /// condition, sorted by ascending score
+    pub fn find_with_scores(&self) -> error::Result<Vec<(T, f64)>> {
+        // the returned data is only valid while the transaction is open
+        let _tx = Tx::new(self.obx_store)?;
+        unsafe {
+            let array = c::new_mut(obx_query_find_with_scores(self.obx_query))?;
+            let mut vec = Vec::new();
+            let count = (*array).count;
+            let bytes_scores = (*array).bytes_scores;
+            if count > 0 && !bytes_scores.is_null() {
+                for bs in slice::from_raw_parts(bytes_scores, count) {
+                    let data_slice = slice::from_raw_parts(bs.data as *const u8, bs.size);
+                    vec.push((make_object(self.helper.as_ref(), data_slice), bs.score));
+                }
+            }
+            obx_bytes_score_array_free(array);
+            Ok(vec)
+        }
+    }
+
+    /// Ids with their query score, sorted by ascending score
+    pub fn find_ids_with_scores(&self) -> error::Result<Vec<(c::obx_id, f64)>> {
+        unsafe {
+            let array = c::new_mut(obx_query_find_ids_with_scores(self.obx_query))?;
+            let count = (*array).count;
+            let ids_scores = (*array).ids_scores;
+            let vec = if count > 0 && !ids_scores.is_null() {
+                slice::from_raw_parts(ids_scores, count)
+                    .iter()
+                    .map(|is| (is.id, is.score))
+                    .collect()
+            } else {
+                Vec::new()
+            };
+            obx_id_score_array_free(array);
+            Ok(vec)
+        }
+    }
+
     fn cursor_count(&self, cursor: &mut OBX_cursor, out_count: *mut u64) -> error::Result<u64> {
         unsafe {
             let code = obx_query_cursor_count(self.obx_query, cursor, out_count);
@@ -253,221 +412,267 @@ impl<T: OBBlanket> Query<T> {
             let count: *mut u64 = &mut 0;
             let err_code = self.cursor_remove(&mut *cursor.obx_cursor, count)?;
             if err_code == 0 {
-                cursor.get_tx().success()?;
+                cursor.get_tx()?.success()?;
             }
             Ok(*count)
         }
     }
     // end cursor
 
-    // TODO implement later
-    // start aliasing
-    /*
-    pub(crate) unsafe fn param_string(
-        &mut self,
-        entity_id: obx_schema_id,
-        property_id: obx_schema_id,
-        value: PtrConstChar,
-    ) -> obx_err {
-        obx_query_param_string(self.obx_query, entity_id, property_id, value)
-    }
+    // start parameters
+    // Change the values of an already built query, either through
+    // the property the condition was made on, or its Condition::alias.
+    // With the property, the condition must be unique on that property.
 
-    // For strings
-    pub(crate) unsafe fn param_2strings(
-        &mut self,
-        entity_id: obx_schema_id,
-        property_id: obx_schema_id,
-        value: PtrConstChar,
-        value2: PtrConstChar,
-    ) -> obx_err {
-        obx_query_param_2strings(self.obx_query, entity_id, property_id, value, value2)
-    }
-
-    pub(crate) unsafe fn param_strings(
-        &mut self,
-        entity_id: obx_schema_id,
-        property_id: obx_schema_id,
-        values: *const PtrConstChar, // ptr ptr === array of CString
-        count: usize,
-    ) -> obx_err {
-        obx_query_param_strings(self.obx_query, entity_id, property_id, values, count)
-    }
-
-    // For ints
-    pub(crate) unsafe fn param_int(
-        &mut self,
-        entity_id: obx_schema_id,
-        property_id: obx_schema_id,
+    pub fn set_param_int<P: BasicExt<T> + ?Sized>(
+        &self,
+        property: &P,
         value: i64,
-    ) -> obx_err {
-        obx_query_param_int(self.obx_query, entity_id, property_id, value)
+    ) -> error::Result<&Self> {
+        let (entity_id, property_id) = (property.get_entity_id(), property.get_property_id());
+        c::call(unsafe { obx_query_param_int(self.obx_query, entity_id, property_id, value) })
+            .map(|_| self)
     }
 
-    pub(crate) unsafe fn param_2ints(
-        &mut self,
-        entity_id: obx_schema_id,
-        property_id: obx_schema_id,
+    /// For between conditions
+    pub fn set_param_ints<P: BasicExt<T> + ?Sized>(
+        &self,
+        property: &P,
         value_a: i64,
         value_b: i64,
-    ) -> obx_err {
-        obx_query_param_2ints(
-            self.obx_query,
-            entity_id,
-            property_id,
-            value_a,
-            value_b,
-        )
-    }
-
-    pub(crate) unsafe fn param_int64s(
-        &mut self,
-        entity_id: obx_schema_id,
-        property_id: obx_schema_id,
-        values: *const i64,
-        count: usize,
-    ) -> obx_err {
-        obx_query_param_int64s(self.obx_query, entity_id, property_id, values, count)
+    ) -> error::Result<&Self> {
+        let (entity_id, property_id) = (property.get_entity_id(), property.get_property_id());
+        c::call(unsafe {
+            obx_query_param_2ints(self.obx_query, entity_id, property_id, value_a, value_b)
+        })
+        .map(|_| self)
     }
 
-    pub(crate) unsafe fn param_int32s(
-        &mut self,
-        entity_id: obx_schema_id,
-        property_id: obx_schema_id,
-        values: *const i32,
-        count: usize,
-    ) -> obx_err {
-        obx_query_param_int32s(self.obx_query, entity_id, property_id, values, count)
-    }
-
-    // For doubles
-    pub(crate) unsafe fn param_double(
-        &mut self,
-        entity_id: obx_schema_id,
-        property_id: obx_schema_id,
+    /// For in/not-in conditions on i64 properties
+    pub fn set_param_int64s<P: BasicExt<T> + ?Sized>(
+        &self,
+        property: &P,
+        values: &[i64],
+    ) -> error::Result<&Self> {
+        let (entity_id, property_id) = (property.get_entity_id(), property.get_property_id());
+        c::call(unsafe {
+            obx_query_param_int64s(
+                self.obx_query,
+                entity_id,
+                property_id,
+                values.as_ptr(),
+                values.len(),
+            )
+        })
+        .map(|_| self)
+    }
+
+    /// For in/not-in conditions on i32 properties
+    pub fn set_param_int32s<P: BasicExt<T> + ?Sized>(
+        &self,
+        property: &P,
+        values: &[i32],
+    ) -> error::Result<&Self> {
+        let (entity_id, property_id) = (property.get_entity_id(), property.get_property_id());
+        c::call(unsafe {
+            obx_query_param_int32s(
+                self.obx_query,
+                entity_id,
+                property_id,
+                values.as_ptr(),
+                values.len(),
+            )
+        })
+        .map(|_| self)
+    }
+
+    pub fn set_param_double<P: BasicExt<T> + ?Sized>(
+        &self,
+        property: &P,
         value: f64,
-    ) -> obx_err {
-        obx_query_param_double(self.obx_query, entity_id, property_id, value)
+    ) -> error::Result<&Self> {
+        let (entity_id, property_id) = (property.get_entity_id(), property.get_property_id());
+        c::call(unsafe { obx_query_param_double(self.obx_query, entity_id, property_id, value) })
+            .map(|_| self)
     }
 
-    pub(crate) unsafe fn param_2doubles(
-        &mut self,
-        entity_id: obx_schema_id,
-        property_id: obx_schema_id,
+    /// For between conditions
+    pub fn set_param_doubles<P: BasicExt<T> + ?Sized>(
+        &self,
+        property: &P,
         value_a: f64,
         value_b: f64,
-    ) -> obx_err {
-        obx_query_param_2doubles(
-            self.obx_query,
-            entity_id,
-            property_id,
-            value_a,
-            value_b,
-        )
-    }
-
-    // For bytes
-    pub(crate) unsafe fn param_bytes(
-        &mut self,
-        entity_id: obx_schema_id,
-        property_id: obx_schema_id,
-        value: ConstVoidPtr,
-        size: usize,
-    ) -> obx_err {
-        obx_query_param_bytes(self.obx_query, entity_id, property_id, value, size)
+    ) -> error::Result<&Self> {
+        let (entity_id, property_id) = (property.get_entity_id(), property.get_property_id());
+        c::call(unsafe {
+            obx_query_param_2doubles(self.obx_query, entity_id, property_id, value_a, value_b)
+        })
+        .map(|_| self)
     }
 
-    pub(crate) unsafe fn param_get_type_size(
-        &mut self,
-        entity_id: obx_schema_id,
-        property_id: obx_schema_id,
-    ) -> usize {
-        obx_query_param_get_type_size(self.obx_query, entity_id, property_id)
+    pub fn set_param_string<P: BasicExt<T> + ?Sized>(
+        &self,
+        property: &P,
+        value: &str,
+    ) -> error::Result<&Self> {
+        let (entity_id, property_id) = (property.get_entity_id(), property.get_property_id());
+        let c_value = to_c_param(value)?;
+        c::call(unsafe {
+            obx_query_param_string(self.obx_query, entity_id, property_id, c_value.as_ptr())
+        })
+        .map(|_| self)
+    }
+
+    /// For in conditions on string properties
+    pub fn set_param_strings<P: BasicExt<T> + ?Sized>(
+        &self,
+        property: &P,
+        values: &[&str],
+    ) -> error::Result<&Self> {
+        let (entity_id, property_id) = (property.get_entity_id(), property.get_property_id());
+        let c_values = values
+            .iter()
+            .map(|v| to_c_param(v))
+            .collect::<error::Result<Vec<CString>>>()?;
+        let ptrs: Vec<PtrConstChar> = c_values.iter().map(|v| v.as_ptr()).collect();
+        c::call(unsafe {
+            obx_query_param_strings(
+                self.obx_query,
+                entity_id,
+                property_id,
+                ptrs.as_ptr(),
+                ptrs.len(),
+            )
+        })
+        .map(|_| self)
+    }
+
+    pub fn set_param_bytes<P: BasicExt<T> + ?Sized>(
+        &self,
+        property: &P,
+        value: &[u8],
+    ) -> error::Result<&Self> {
+        let (entity_id, property_id) = (property.get_entity_id(), property.get_property_id());
+        c::call(unsafe {
+            obx_query_param_bytes(
+                self.obx_query,
+                entity_id,
+                property_id,
+                value.as_ptr() as ConstVoidPtr,
+                value.len(),
+            )
+        })
+        .map(|_| self)
     }
 
     // For aliases
-    pub(crate) unsafe fn param_alias_string(
-        &mut self,
-        alias: PtrConstChar,
-        value: PtrConstChar,
-    ) -> obx_err {
-        obx_query_param_alias_string(self.obx_query, alias, value)
-    }
 
-    pub(crate) unsafe fn param_alias_strings(
-        &mut self,
-        alias: PtrConstChar,
-        values: *const PtrConstChar,
-        count: usize,
-    ) -> obx_err {
-        obx_query_param_alias_strings(self.obx_query, alias, values, count)
+    pub fn set_param_alias_int(&self, alias: &str, value: i64) -> error::Result<&Self> {
+        let c_alias = to_c_param(alias)?;
+        c::call(unsafe { obx_query_param_alias_int(self.obx_query, c_alias.as_ptr(), value) })
+            .map(|_| self)
     }
 
-    pub(crate) unsafe fn param_alias_int(
-        &mut self,
-        alias: PtrConstChar,
-        value: i64,
-    ) -> obx_err {
-        obx_query_param_alias_int(self.obx_query, alias, value)
-    }
-
-    pub(crate) unsafe fn param_alias_2ints(
-        &mut self,
-        alias: PtrConstChar,
+    pub fn set_param_alias_ints(
+        &self,
+        alias: &str,
         value_a: i64,
         value_b: i64,
-    ) -> obx_err {
-        obx_query_param_alias_2ints(self.obx_query, alias, value_a, value_b)
-    }
-
-    pub(crate) unsafe fn param_alias_int64s(
-        &mut self,
-        alias: PtrConstChar,
-        values: *const i64,
-        count: usize,
-    ) -> obx_err {
-        obx_query_param_alias_int64s(self.obx_query, alias, values, count)
-    }
-
-    pub(crate) unsafe fn param_alias_int32s(
-        &mut self,
-        alias: PtrConstChar,
-        values: *const i32,
-        count: usize,
-    ) -> obx_err {
-        obx_query_param_alias_int32s(self.obx_query, alias, values, count)
-    }
-
-    pub(crate) unsafe fn param_alias_double(
-        &mut self,
-        alias: PtrConstChar,
-        value: f64,
-    ) -> obx_err {
-        obx_query_param_alias_double(self.obx_query, alias, value)
-    }
-
-    pub(crate) unsafe fn param_alias_2doubles(
-        &mut self,
-        alias: PtrConstChar,
+    ) -> error::Result<&Self> {
+        let c_alias = to_c_param(alias)?;
+        c::call(unsafe {
+            obx_query_param_alias_2ints(self.obx_query, c_alias.as_ptr(), value_a, value_b)
+        })
+        .map(|_| self)
+    }
+
+    pub fn set_param_alias_int64s(&self, alias: &str, values: &[i64]) -> error::Result<&Self> {
+        let c_alias = to_c_param(alias)?;
+        c::call(unsafe {
+            obx_query_param_alias_int64s(
+                self.obx_query,
+                c_alias.as_ptr(),
+                values.as_ptr(),
+                values.len(),
+            )
+        })
+        .map(|_| self)
+    }
+
+    pub fn set_param_alias_int32s(&self, alias: &str, values: &[i32]) -> error::Result<&Self> {
+        let c_alias = to_c_param(alias)?;
+        c::call(unsafe {
+            obx_query_param_alias_int32s(
+                self.obx_query,
+                c_alias.as_ptr(),
+                values.as_ptr(),
+                values.len(),
+            )
+        })
+        .map(|_| self)
+    }
+
+    pub fn set_param_alias_double(&self, alias: &str, value: f64) -> error::Result<&Self> {
+        let c_alias = to_c_param(alias)?;
+        c::call(unsafe { obx_query_param_alias_double(self.obx_query, c_alias.as_ptr(), value) })
+            .map(|_| self)
+    }
+
+    pub fn set_param_alias_doubles(
+        &self,
+        alias: &str,
         value_a: f64,
         value_b: f64,
-    ) -> obx_err {
-        obx_query_param_alias_2doubles(self.obx_query, alias, value_a, value_b)
-    }
-
-    pub(crate) unsafe fn param_alias_bytes(
-        &mut self,
-        alias: PtrConstChar,
-        value: ConstVoidPtr,
-        size: usize,
-    ) -> obx_err {
-        obx_query_param_alias_bytes(self.obx_query, alias, value, size)
-    }
+    ) -> error::Result<&Self> {
+        let c_alias = to_c_param(alias)?;
+        c::call(unsafe {
+            obx_query_param_alias_2doubles(self.obx_query, c_alias.as_ptr(), value_a, value_b)
+        })
+        .map(|_| self)
+    }
+
+    pub fn set_param_alias_string(&self, alias: &str, value: &str) -> error::Result<&Self> {
+        let c_alias = to_c_param(alias)?;
+        let c_value = to_c_param(value)?;
+        c::call(unsafe {
+            obx_query_param_alias_string(self.obx_query, c_alias.as_ptr(), c_value.as_ptr())
+        })
+        .map(|_| self)
+    }
+
+    pub fn set_param_alias_strings(&self, alias: &str, values: &[&str]) -> error::Result<&Self> {
+        let c_alias = to_c_param(alias)?;
+        let c_values = values
+            .iter()
+            .map(|v| to_c_param(v))
+            .collect::<error::Result<Vec<CString>>>()?;
+        let ptrs: Vec<PtrConstChar> = c_values.iter().map(|v| v.as_ptr()).collect();
+        c::call(unsafe {
+            obx_query_param_alias_strings(
+                self.obx_query,
+                c_alias.as_ptr(),
+                ptrs.as_ptr(),
+                ptrs.len(),
+            )
+        })
+        .map(|_| self)
+    }
+
+    pub fn set_param_alias_bytes(&self, alias: &str, value: &[u8]) -> error::Result<&Self> {
+        let c_alias = to_c_param(alias)?;
+        c::call(unsafe {
+            obx_query_param_alias_bytes(
+                self.obx_query,
+                c_alias.as_ptr(),
+                value.as_ptr() as ConstVoidPtr,
+                value.len(),
+            )
+        })
+        .map(|_| self)
+    }
+    // end parameters
+}
 
-    pub(crate) unsafe fn param_alias_get_type_size(
-        &mut self,
-        alias: PtrConstChar,
-    ) -> usize {
-        obx_query_param_alias_get_type_size(self.obx_query, alias)
-    }
-    */
+fn to_c_param(s: &str) -> error::Result<CString> {
+    to_c_string(s).ok_or_else(|| error::Error::new_local("Error: query: invalid parameter string"))
 }
@@ -23,6 +23,21 @@ pub fn create_condition_builder<
     ConditionBuilder {
         phantom_data: PhantomData,
         ids_and_type: Rc::new((ENTITY_ID, PROPERTY_ID, PROPERTY_TYPE)),
+        hnsw_dimensions: 0,
+    }
+}
+
+/// Same as `create_condition_builder`, for a vector property with an #[hnsw] index
+pub fn create_vector_condition_builder<
+    T: OBBlanket,
+    const ENTITY_ID: c::obx_schema_id,
+    const PROPERTY_ID: c::obx_schema_id,
+    const PROPERTY_TYPE: c::OBXPropertyType,
+    const DIMENSIONS: usize,
+>() -> ConditionBuilder<T> {
+    ConditionBuilder {
+        hnsw_dimensions: DIMENSIONS,
+        ..create_condition_builder::<T, ENTITY_ID, PROPERTY_ID, PROPERTY_TYPE>()
     }
 }
 
@@ -32,6 +47,8 @@ pub struct ConditionBuilder<Entity: OBBlanket> {
     phantom_data: PhantomData<Entity>,
     // entity_id: obx_schema_id, property_id: obx_schema_id, property_type: u8,
     ids_and_type: IdsAndType,
+    // 0 without an #[hnsw] index
+    hnsw_dimensions: usize,
 }
 
 impl<Entity: OBBlanket> ConditionBuilder<Entity> {
@@ -41,6 +58,11 @@ impl<Entity: OBBlanket> ConditionBuilder<Entity> {
 }
 
 pub trait BasicExt<Entity: OBBlanket> {
+    fn get_entity_id(&self) -> c::obx_schema_id;
+    fn get_property_id(&self) -> c::obx_schema_id;
+    fn get_property_type(&self) -> c::OBXPropertyType;
+
+    /// Prefer `Builder::order_by`, which takes typed flags and reports errors
     fn order_flags(&mut self, of: u32) -> Condition<Entity>;
 
     // TODO test when there is support for Option<*> properties
@@ -50,6 +72,16 @@ pub trait BasicExt<Entity: OBBlanket> {
 }
 
 impl<Entity: OBBlanket> BasicExt<Entity> for ConditionBuilder<Entity> {
+    fn get_entity_id(&self) -> c::obx_schema_id {
+        self.ids_and_type.0
+    }
+    fn get_property_id(&self) -> c::obx_schema_id {
+        self.ids_and_type.1
+    }
+    fn get_property_type(&self) -> c::OBXPropertyType {
+        self.ids_and_type.2
+    }
+
     fn order_flags(&mut self, of: u32) -> Condition<Entity> {
         Condition::new(self.get_property_attrs(), ConditionOp::OrderFlags(of))
     }
@@ -151,6 +183,22 @@ impl<Entity: OBBlanket> StringExt<Entity> for ConditionBuilder<Entity> {
     }
 }
 
+pub trait VectorExt<Entity: OBBlanket> {
+    /// Approximate nearest neighbor search, the property needs an #[hnsw] index.
+    /// Combine with Query::find_with_scores to get the distances.
+    /// Building the query fails if the vector length is not the index dimensions.
+    fn nearest_neighbors(&self, vector: Vec<f32>, max_result_count: usize) -> Condition<Entity>;
+}
+
+impl<Entity: OBBlanket> VectorExt<Entity> for ConditionBuilder<Entity> {
+    fn nearest_neighbors(&self, vector: Vec<f32>, max_result_count: usize) -> Condition<Entity> {
+        Condition::new(
+            self.get_property_attrs(),
+            ConditionOp::NearestNeighbors_f32(vector, max_result_count, self.hnsw_dimensions),
+        )
+    }
+}
+
 pub trait BetweenExt<Entity: OBBlanket, SurroundType>
 where
     SurroundType: ?Sized,
@@ -391,6 +439,14 @@ pub trait VecU8Blanket<Entity: OBBlanket>:
 {
 }
 
+pub trait VecF32Blanket<Entity: OBBlanket>: BasicExt<Entity> + VectorExt<Entity> {}
+
+/// Scalar vectors (other than Vec<f32>), the C API has no element conditions for these
+pub trait VecBlanket<Entity: OBBlanket>: BasicExt<Entity> {}
+
+/// Vec<String>, contains/contains_element/any_equals match on the elements
+pub trait VecStringBlanket<Entity: OBBlanket>: BasicExt<Entity> + StringExt<Entity> {}
+
 pub trait StringBlanket<Entity: OBBlanket>:
     StringExt<Entity>
     + BasicExt<Entity>
@@ -488,6 +544,15 @@ impl<Entity: OBBlanket> VecU8Blanket<Entity> for Entity where
     Entity: BasicExt<Entity> + EqExt<Entity, Vec<u8>> + OrdExt<Entity, Vec<u8>>
 {
 }
+impl<Entity: OBBlanket> VecF32Blanket<Entity> for Entity where
+    Entity: BasicExt<Entity> + VectorExt<Entity>
+{
+}
+impl<Entity: OBBlanket> VecBlanket<Entity> for Entity where Entity: BasicExt<Entity> {}
+impl<Entity: OBBlanket> VecStringBlanket<Entity> for Entity where
+    Entity: BasicExt<Entity> + StringExt<Entity>
+{
+}
 impl<Entity: OBBlanket> StringBlanket<Entity> for Entity where
     Entity: StringExt<Entity>
         + BasicExt<Entity>
@@ -572,11 +637,13 @@ mod tests {
         let cb1: ConditionBuilder<TEntity> = ConditionBuilder {
             phantom_data: PhantomData,
             ids_and_type: Rc::new((1, 1, 1)),
+            hnsw_dimensions: 0,
         };
 
         let mut cb2: ConditionBuilder<TEntity2> = ConditionBuilder {
             phantom_data: PhantomData,
             ids_and_type: Rc::new((2, 2, 2)),
+            hnsw_dimensions: 0,
         };
 
         let boxed_cb1 = Box::new(cb1);
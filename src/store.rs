@@ -1,58 +1,202 @@
 #![allow(dead_code)]
-use std::ffi::CString;
+use std::ffi::{c_void, CString};
+use std::panic::{self, AssertUnwindSafe};
 use std::path::Path;
-use std::rc::Rc;
-
-use anymap::AnyMap;
+use std::sync::mpsc::{self, Receiver};
+use std::sync::Arc;
 
+use crate::backup::BackupFlags;
 use crate::c::{self, *};
 use crate::error::{self, Error};
 
+use crate::observer::Observer;
 use crate::opt::Opt;
-use crate::traits::{EntityFactoryExt, OBBlanket};
+use crate::traits::{EntityFactoryExt, FactoryMap, OBBlanket};
+use crate::txn::{Transaction, Tx};
 
 // Caveat: copy and drop are mutually exclusive
 
+/// The native store is thread-safe, share it via `Arc<Store>` or `try_clone`.
+/// Boxes, queries and transactions are not, each thread gets its own with `get_box`.
 pub struct Store {
-    pub trait_map: AnyMap, // passed as a ref to a Box
+    pub trait_map: Arc<FactoryMap>, // passed as a ref to a Box
     // TODO confirm: model and opt are cleaned up already and zero'ed, or else we'll have a double-free
     pub(crate) obx_store: *mut OBX_store, // TODO confirm: model and opt are cleaned up already
 }
 
+unsafe impl Send for Store {}
+unsafe impl Sync for Store {}
+
 impl Drop for Store {
     fn drop(&mut self) {
         if !self.obx_store.is_null() {
-            match self.prepare_then_close() {
-                Err(err) => eprintln!("Error: store: {err}"),
-                _ => (),
+            // the native side closes the store with its last handle,
+            // see try_clone and WeakStore::upgrade
+            if let Err(err) = self.close() {
+                eprintln!("Error: store: {err}");
             }
             self.obx_store = std::ptr::null_mut();
         }
     }
 }
 
-// TODO Bonus: start admin http in debug from store?
+type StatusCallback = std::boxed::Box<dyn FnOnce(error::Result<()>) + Send>;
+
+// Takes back ownership of the closure, it runs only once
+unsafe extern "C" fn status_trampoline(status: obx_err, user_data: *mut c_void) {
+    let f = std::boxed::Box::from_raw(user_data as *mut StatusCallback);
+    let r = panic::catch_unwind(AssertUnwindSafe(move || {
+        f(match status {
+            0 | 404 => Ok(()),
+            _ => Err(c::status_error(status, "store")),
+        })
+    }));
+    if r.is_err() {
+        eprintln!("Error: store: callback panicked");
+    }
+}
 
 impl Store {
     /// Assumes ownership of map, and Opt,
-    pub fn new(mut opt: Opt, map: AnyMap) -> error::Result<Self> {
+    pub fn new(mut opt: Opt, map: FactoryMap) -> error::Result<Self> {
         let obx_store = c::new_mut(unsafe { obx_store_open(opt.obx_opt) })?;
         // This prevents a double free
         opt.ptr_consumed = !obx_store.is_null();
         let r = Store {
-            trait_map: map,
+            trait_map: Arc::new(map),
             obx_store,
         };
         Ok(r)
     }
 
-    pub fn get_box<T: 'static + OBBlanket>(&self) -> error::Result<crate::r#box::Box<T>> {
-        let helper = if let Some(h) = self.trait_map.get::<Rc<dyn EntityFactoryExt<T>>>() {
-            h
+    pub(crate) fn get_helper<T: 'static>(&self) -> error::Result<Arc<dyn EntityFactoryExt<T>>> {
+        if let Some(h) = self.trait_map.get::<Arc<dyn EntityFactoryExt<T>>>() {
+            Ok(h.clone())
         } else {
-            Error::new_local("Error: unable to get entity helper").as_result()?
-        };
-        Ok(crate::r#box::Box::<T>::new(self.obx_store, helper.clone()))
+            Error::new_local("Error: unable to get entity helper").as_result()
+        }
+    }
+
+    pub fn get_box<T: 'static + OBBlanket>(&self) -> error::Result<crate::r#box::Box<T>> {
+        let helper = self.get_helper::<T>()?;
+        Ok(crate::r#box::Box::<T>::new(self.obx_store, helper))
+    }
+
+    /// The schema id of the entity type, e.g. to match the type ids from `observe_channel`
+    pub fn entity_id_of<T: 'static>(&self) -> error::Result<obx_schema_id> {
+        self.get_helper::<T>().map(|h| h.get_entity_id())
+    }
+
+    /// Calls `f` after each committed transaction that changed objects of type T.
+    /// `f` runs on a background thread, the observer stops when the `Observer` is dropped.
+    pub fn observe<T: 'static>(
+        &self,
+        f: impl FnMut() + Send + 'static,
+    ) -> error::Result<Observer<'_>> {
+        let type_id = self.entity_id_of::<T>()?;
+        Observer::new_single_type(self, type_id, std::boxed::Box::new(f))
+    }
+
+    /// Calls `f` with the changed type ids after each committed transaction.
+    pub fn observe_all(
+        &self,
+        f: impl FnMut(&[obx_schema_id]) + Send + 'static,
+    ) -> error::Result<Observer<'_>> {
+        Observer::new_types(self, std::boxed::Box::new(f))
+    }
+
+    /// Like `observe_all`, but the changed type ids are sent over a channel.
+    /// Changes are no longer sent after the `Observer` is dropped.
+    pub fn observe_channel(&self) -> error::Result<(Observer<'_>, Receiver<Vec<obx_schema_id>>)> {
+        let (sender, receiver) = mpsc::channel();
+        let observer = self.observe_all(move |type_ids| {
+            // the receiver might be gone already, nothing to do then
+            let _ = sender.send(type_ids.to_vec());
+        })?;
+        Ok((observer, receiver))
+    }
+
+    /// Runs the closure inside a single read transaction.
+    /// Boxes fetched from the Transaction share it.
+    pub fn read_tx<R, F>(&self, f: F) -> error::Result<R>
+    where
+        F: FnOnce(&Transaction) -> error::Result<R>,
+    {
+        let tx = Transaction::new(self, false)?;
+        f(&tx)
+    }
+
+    /// Runs the closure inside a single write transaction.
+    /// The transaction is committed when the closure returns `Ok`,
+    /// and aborted when it returns `Err` or panics.
+    pub fn write_tx<R, F>(&self, f: F) -> error::Result<R>
+    where
+        F: FnOnce(&Transaction) -> error::Result<R>,
+    {
+        let mut tx = Transaction::new(self, true)?;
+        match panic::catch_unwind(AssertUnwindSafe(|| f(&tx))) {
+            Ok(Ok(r)) => {
+                tx.tx.success()?;
+                Ok(r)
+            }
+            Ok(Err(err)) => {
+                if let Err(abort_err) = tx.tx.abort() {
+                    eprintln!("Error: txn: {abort_err}");
+                }
+                Err(err)
+            }
+            Err(payload) => {
+                if let Err(abort_err) = tx.tx.abort() {
+                    eprintln!("Error: txn: {abort_err}");
+                }
+                drop(tx);
+                panic::resume_unwind(payload)
+            }
+        }
+    }
+
+    /// Removes the objects of type T whose `#[expiration_time]` has passed,
+    /// returns the number of removed objects.
+    pub fn remove_expired<T: 'static>(&self) -> error::Result<u64> {
+        let entity_id = self.entity_id_of::<T>()?;
+        let mut tx = Tx::new_mut(self.obx_store)?;
+        let mut count: usize = 0;
+        c::call(unsafe { obx_expired_objects_remove(tx.obx_txn, entity_id, &mut count) })?;
+        tx.success()?;
+        Ok(count as u64)
+    }
+
+    /// Like `remove_expired`, on a background thread.
+    /// `done` receives the outcome, on that thread.
+    pub fn remove_expired_async<T: 'static>(
+        &self,
+        done: impl FnOnce(error::Result<()>) + Send + 'static,
+    ) -> error::Result<()> {
+        let entity_id = self.entity_id_of::<T>()?;
+        let boxed: std::boxed::Box<StatusCallback> =
+            std::boxed::Box::new(std::boxed::Box::new(done));
+        let user_data = std::boxed::Box::into_raw(boxed) as *mut c_void;
+        let r = c::call(unsafe {
+            obx_expired_objects_remove_async(
+                self.obx_store,
+                entity_id,
+                Some(status_trampoline),
+                user_data,
+            )
+        });
+        if r.is_err() {
+            // not enqueued, the callback won't run
+            drop(unsafe { std::boxed::Box::from_raw(user_data as *mut StatusCallback) });
+        }
+        r
+    }
+
+    /// Writes a backup of the whole database to the given file, while the store stays open.
+    /// Only available in the server edition of the native library.
+    pub fn backup_to(&self, path: &Path, flags: BackupFlags) -> error::Result<()> {
+        let c_str = CString::new(path.to_str().unwrap_or(""))
+            .map_err(|e| Error::new_local(&format!("Invalid backup path: {}", e)))?;
+        c::call(unsafe { obx_store_back_up_to_file(self.obx_store, c_str.as_ptr(), flags.bits()) })
     }
 
     pub fn is_open(path: &Path) -> bool {
@@ -62,29 +206,29 @@ impl Store {
 
     /// Attach to an already-open store at the given path.
     /// Use this for concurrent access when another process (e.g. Flutter) already has the store open.
-    pub fn attach(path: &Path, map: AnyMap) -> error::Result<Self> {
+    pub fn attach(path: &Path, map: FactoryMap) -> error::Result<Self> {
         let c_str = CString::new(path.to_str().unwrap_or(""))
             .map_err(|e| Error::new_local(&format!("Invalid path: {}", e)))?;
         let obx_store = c::new_mut(unsafe { obx_store_attach(c_str.as_ptr()) })?;
         Ok(Store {
             obx_store,
-            trait_map: map,
+            trait_map: Arc::new(map),
         })
     }
 
     /// Attach to an existing store by its store ID.
     /// Useful for sharing a store across threads within the same process.
-    pub fn attach_by_id(store_id: u64, map: AnyMap) -> error::Result<Self> {
+    pub fn attach_by_id(store_id: u64, map: FactoryMap) -> error::Result<Self> {
         let obx_store = c::new_mut(unsafe { obx_store_attach_id(store_id) })?;
         Ok(Store {
             obx_store,
-            trait_map: map,
+            trait_map: Arc::new(map),
         })
     }
 
     /// Try to attach to an existing store first; if none is open, open a new one.
     /// Returns the store and a flag indicating whether it was attached (true) or newly opened (false).
-    pub fn attach_or_open(mut opt: Opt, map: AnyMap) -> error::Result<(Self, bool)> {
+    pub fn attach_or_open(mut opt: Opt, map: FactoryMap) -> error::Result<(Self, bool)> {
         let mut out_attached = false;
         let obx_store = c::new_mut(unsafe {
             obx_store_attach_or_open(opt.obx_opt, false, &mut out_attached)
@@ -93,7 +237,7 @@ impl Store {
         Ok((
             Store {
                 obx_store,
-                trait_map: map,
+                trait_map: Arc::new(map),
             },
             out_attached,
         ))
@@ -104,20 +248,32 @@ impl Store {
         unsafe { obx_store_id(self.obx_store) }
     }
 
-    // TODO impl without Copy/Clone trait, because Drop, then use over channels
-    // pub fn clone(&self) -> Self {
-    //   Store {
-    //     obx_store: unsafe { obx_store_clone(self.obx_store) },
-    //     error: None,
-    //   }
-    // }
+    /// A new handle to the same store, sharing the entity factories,
+    /// e.g. to move into a worker thread or task that may outlive this one.
+    /// The store stays open until the last handle is dropped, the original included.
+    pub fn try_clone(&self) -> error::Result<Self> {
+        let obx_store = c::new_mut(unsafe { obx_store_clone(self.obx_store) })?;
+        Ok(Store {
+            obx_store,
+            trait_map: self.trait_map.clone(),
+        })
+    }
+
+    /// A weak reference that does not keep the store open, see `WeakStore::upgrade`
+    pub fn downgrade(&self) -> error::Result<WeakStore> {
+        let obx_weak_store = c::new_mut(unsafe { obx_weak_store(self.obx_store) })?;
+        Ok(WeakStore {
+            obx_weak_store,
+            trait_map: self.trait_map.clone(),
+        })
+    }
 
-    pub fn from_core_wrap(core_store: &mut Vec<u8>, map: AnyMap) -> error::Result<Self> {
+    pub fn from_core_wrap(core_store: &mut Vec<u8>, map: FactoryMap) -> error::Result<Self> {
         // TODO test
         let ptr = unsafe { obx_store_wrap(core_store.as_ptr() as *mut std::ffi::c_void) };
         c::new_mut(ptr).map(|s| Store {
             obx_store: s,
-            trait_map: map,
+            trait_map: Arc::new(map),
         })
     }
 
@@ -177,3 +333,68 @@ impl Store {
         self.prepare_to_close()?.close()
     }
 }
+
+/// A weak reference to a Store, for long-lived background components
+/// (observers, schedulers) that must not keep the store from closing.
+/// Use `upgrade` to get a Store for the duration of the work.
+pub struct WeakStore {
+    obx_weak_store: *mut OBX_weak_store,
+    trait_map: Arc<FactoryMap>,
+}
+
+unsafe impl Send for WeakStore {}
+unsafe impl Sync for WeakStore {}
+
+impl Drop for WeakStore {
+    fn drop(&mut self) {
+        if !self.obx_weak_store.is_null() {
+            unsafe { obx_weak_store_free(self.obx_weak_store) };
+            self.obx_weak_store = std::ptr::null_mut();
+        }
+    }
+}
+
+impl WeakStore {
+    /// The weak counterpart of `Store::attach_by_id`,
+    /// None if no store with this id is open
+    pub fn by_id(store_id: u64, map: FactoryMap) -> Option<Self> {
+        let obx_weak_store = unsafe { obx_weak_store_by_id(store_id) };
+        if obx_weak_store.is_null() {
+            return None;
+        }
+        Some(WeakStore {
+            obx_weak_store,
+            trait_map: Arc::new(map),
+        })
+    }
+
+    /// A Store that keeps the store open until dropped,
+    /// None if the store was closed already (all strong references are gone)
+    pub fn upgrade(&self) -> Option<Store> {
+        let obx_store = unsafe { obx_weak_store_lock(self.obx_weak_store) };
+        if obx_store.is_null() {
+            return None;
+        }
+        Some(Store {
+            obx_store,
+            trait_map: self.trait_map.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorKind;
+
+    #[test]
+    fn status_trampoline_passes_the_status_kind() {
+        let (sender, receiver) = mpsc::channel();
+        let done: StatusCallback = std::boxed::Box::new(move |r| sender.send(r).unwrap());
+        let user_data = std::boxed::Box::into_raw(std::boxed::Box::new(done)) as *mut c_void;
+        unsafe { status_trampoline(OBX_ERROR_ILLEGAL_STATE as obx_err, user_data) };
+
+        let err = receiver.recv().unwrap().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::IllegalState);
+    }
+}
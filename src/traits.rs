@@ -33,16 +33,26 @@ impl<T> OBBlanket for T where T: IdExt + FBOBBridge {}
 
 use flatbuffers::Table;
 
-pub trait EntityFactoryExt<T: ?Sized> {
+// Send + Sync, so a Store and its factories can be shared between threads
+pub trait EntityFactoryExt<T: ?Sized>: Send + Sync {
     fn make(&self, table: &mut Table) -> T;
     fn get_entity_id(&self) -> c::obx_schema_id;
     fn new_entity(&self) -> T;
+    /// The type of the `#[id_companion]` property, Date or DateNano, None without one
+    fn get_id_companion_type(&self) -> Option<c::OBXPropertyType> {
+        None
+    }
 }
 pub struct Factory<T> {
-    pub phantom_data: PhantomData<T>,
+    // fn() -> T: Send + Sync for any entity, it's never stored
+    pub phantom_data: PhantomData<fn() -> T>,
     pub schema_id: c::obx_schema_id,
 }
 
+/// The entity factories of a model, keyed by `Arc<dyn EntityFactoryExt<T>>`,
+/// see the generated `make_factory_map`
+pub type FactoryMap = anymap::Map<dyn anymap::any::Any + Send + Sync>;
+
 pub fn make_from_trait<T>(map: anymap::AnyMap, table: &mut Table) -> Option<T>
 where
     T: 'static,
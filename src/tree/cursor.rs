@@ -0,0 +1,264 @@
+use std::ffi::{c_void, CStr};
+use std::marker::PhantomData;
+use std::ptr;
+use std::slice;
+
+use crate::c::{self, *};
+use crate::error::{self, Error, ErrorKind};
+use crate::r#box::PutMode;
+use crate::txn::Tx;
+use crate::util::NOT_FOUND_404;
+
+use super::leaf::{self, LeafValue};
+use super::{leaf_name, to_c_path, Tree};
+
+extern "C" {
+    // obx_tree_cursor_get_leaf_path allocates with malloc
+    fn free(ptr: *mut c_void);
+}
+
+/// A leaf under a path, see `TreeCursor::get_child_leaves_info`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LeafInfo {
+    pub id: obx_id,
+    pub path: String,
+    /// OBXPropertyType_Unknown without a meta leaf
+    pub value_type: OBXPropertyType,
+}
+
+/// The raw FlatBuffers of a leaf, copied out of the transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawLeaf {
+    pub data: Vec<u8>,
+    pub metadata: Option<Vec<u8>>,
+}
+
+/// Path-addressed access to a Tree, inside its own transaction.
+/// A write cursor only persists its changes with `commit`,
+/// dropping it aborts the transaction.
+pub struct TreeCursor<'t> {
+    obx_tree_cursor: *mut OBX_tree_cursor,
+    // closed after the cursor
+    tx: Tx,
+    path_delimiter: char,
+    phantom_data: PhantomData<&'t ()>,
+}
+
+impl Drop for TreeCursor<'_> {
+    fn drop(&mut self) {
+        if !self.obx_tree_cursor.is_null() {
+            unsafe { obx_tree_cursor_close(self.obx_tree_cursor) };
+            self.obx_tree_cursor = ptr::null_mut();
+        }
+    }
+}
+
+impl<'t> TreeCursor<'t> {
+    pub(crate) fn new(tree: &'t Tree<'_>, is_mut: bool) -> error::Result<Self> {
+        let tx = if is_mut {
+            Tx::new_mut(tree.obx_store)
+        } else {
+            Tx::new(tree.obx_store)
+        }?;
+        let obx_tree_cursor = c::new_mut(unsafe { obx_tree_cursor(tree.obx_tree, tx.obx_txn) })?;
+        Ok(TreeCursor {
+            obx_tree_cursor,
+            tx,
+            path_delimiter: tree.options.path_delimiter,
+            phantom_data: PhantomData,
+        })
+    }
+
+    /// Commits the changes of a write cursor
+    pub fn commit(mut self) -> error::Result<()> {
+        // the cursor must let go of the transaction before it ends
+        c::call(unsafe { obx_tree_cursor_txn(self.obx_tree_cursor, ptr::null_mut()) })?;
+        self.tx.success()
+    }
+
+    /// The data leaf and, if asked for, its meta leaf, None if the path does not exist
+    pub fn get_raw(&self, path: &str, with_metadata: bool) -> error::Result<Option<RawLeaf>> {
+        let c_path = to_c_path(path)?;
+        let mut data: *const c_void = ptr::null();
+        let mut size: usize = 0;
+        let mut metadata: *const c_void = ptr::null();
+        let mut metadata_size: usize = 0;
+        let code = unsafe {
+            obx_tree_cursor_get_raw(
+                self.obx_tree_cursor,
+                c_path.as_ptr(),
+                &mut data,
+                &mut size,
+                if with_metadata {
+                    &mut metadata
+                } else {
+                    ptr::null_mut()
+                },
+                &mut metadata_size,
+            )
+        };
+        c::call(code)?;
+        if code == NOT_FOUND_404 || data.is_null() {
+            return Ok(None);
+        }
+        // only valid until the transaction ends, copy
+        let copy = |p: *const c_void, len: usize| unsafe {
+            slice::from_raw_parts(p as *const u8, len).to_vec()
+        };
+        Ok(Some(RawLeaf {
+            data: copy(data, size),
+            metadata: if metadata.is_null() {
+                None
+            } else {
+                Some(copy(metadata, metadata_size))
+            },
+        }))
+    }
+
+    /// Puts the data leaf, see `LeafValue::to_data_leaf`.
+    /// Without `metadata` the branches and meta leaf must exist already,
+    /// returns None otherwise. With `metadata` (see `leaf::to_meta_leaf`),
+    /// missing branches and meta nodes are created on the fly.
+    /// Fails with ErrorKind::NoSuccess if `mode` prevented the put.
+    pub fn put_raw(
+        &self,
+        path: &str,
+        data: &mut [u8],
+        value_type: OBXPropertyType,
+        metadata: Option<&mut [u8]>,
+        mode: PutMode,
+    ) -> error::Result<Option<obx_id>> {
+        let c_path = to_c_path(path)?;
+        let (metadata_ptr, metadata_size) = match metadata {
+            Some(m) => (m.as_mut_ptr() as *mut c_void, m.len()),
+            None => (ptr::null_mut(), 0),
+        };
+        let mut id: obx_id = 0;
+        let code = unsafe {
+            obx_tree_cursor_put_raw(
+                self.obx_tree_cursor,
+                c_path.as_ptr(),
+                data.as_mut_ptr() as *mut c_void,
+                data.len(),
+                value_type,
+                &mut id,
+                metadata_ptr,
+                metadata_size,
+                mode.to_obx(),
+            )
+        };
+        if code == OBX_NO_SUCCESS as obx_err {
+            return Err(Error::new_local_kind(
+                ErrorKind::NoSuccess,
+                "Error: tree: the put mode prevented the put",
+            ));
+        }
+        c::call(code)?;
+        Ok(if code == NOT_FOUND_404 {
+            None
+        } else {
+            Some(id)
+        })
+    }
+
+    /// The typed value at the path, None if it does not exist
+    pub fn get(&self, path: &str) -> error::Result<Option<LeafValue>> {
+        Ok(self.get_raw(path, true)?.and_then(|raw| {
+            // copied from the native side
+            unsafe {
+                let value_type = raw
+                    .metadata
+                    .as_deref()
+                    .map_or(c::OBXPropertyType_Unknown, |m| leaf::meta_leaf_value_type(m));
+                LeafValue::from_data_leaf(&raw.data, value_type)
+            }
+        }))
+    }
+
+    /// Puts the value at the path, creating missing branches on the way.
+    /// Returns the leaf id.
+    pub fn put(&self, path: &str, value: impl Into<LeafValue>) -> error::Result<obx_id> {
+        self.put_with_mode(path, value, PutMode::Put)
+    }
+
+    pub fn put_with_mode(
+        &self,
+        path: &str,
+        value: impl Into<LeafValue>,
+        mode: PutMode,
+    ) -> error::Result<obx_id> {
+        let value = value.into();
+        let value_type = value.property_type();
+        // the common case: the leaf was put before, the meta leaf exists
+        if let Some(id) = self.put_raw(path, &mut value.to_data_leaf(), value_type, None, mode)? {
+            return Ok(id);
+        }
+        let mut metadata = leaf::to_meta_leaf(leaf_name(path, self.path_delimiter), value_type);
+        self.put_raw(
+            path,
+            &mut value.to_data_leaf(),
+            value_type,
+            Some(&mut metadata),
+            mode,
+        )?
+        .ok_or_else(|| Error::new_local("Error: tree: unable to put the leaf"))
+    }
+
+    /// The full path of a leaf, None if there is no leaf with this id
+    pub fn get_leaf_path(&self, leaf_id: obx_id) -> error::Result<Option<String>> {
+        let path = unsafe { obx_tree_cursor_get_leaf_path(self.obx_tree_cursor, leaf_id) };
+        if path.is_null() {
+            return Ok(None);
+        }
+        let s = unsafe { CStr::from_ptr(path) }
+            .to_string_lossy()
+            .into_owned();
+        unsafe { free(path as *mut c_void) };
+        Ok(Some(s))
+    }
+
+    /// All leaves under the path (the root if None), ordered by depth, then by id
+    pub fn get_child_leaves_info(&self, path: Option<&str>) -> error::Result<Vec<LeafInfo>> {
+        let c_path = path.map(to_c_path).transpose()?;
+        let path_ptr = c_path.as_ref().map_or(ptr::null(), |p| p.as_ptr());
+        unsafe {
+            let info = c::new_mut(obx_tree_cursor_get_child_leaves_info(
+                self.obx_tree_cursor,
+                path_ptr,
+            ))?;
+            let leaves = (0..obx_tree_leaves_info_size(info))
+                .map(|i| LeafInfo {
+                    id: obx_tree_leaves_info_id(info, i),
+                    path: CStr::from_ptr(obx_tree_leaves_info_path(info, i))
+                        .to_string_lossy()
+                        .into_owned(),
+                    value_type: obx_tree_leaves_info_type(info, i),
+                })
+                .collect();
+            obx_tree_leaves_info_free(info);
+            Ok(leaves)
+        }
+    }
+
+    /// Merges nodes of the same kind sharing a path, returns how many were consolidated.
+    /// Requires a write cursor.
+    pub fn consolidate_node_conflicts(&self) -> error::Result<usize> {
+        let mut count: usize = 0;
+        c::call(unsafe {
+            obx_tree_cursor_consolidate_node_conflicts(self.obx_tree_cursor, &mut count)
+        })
+        .map(|_| count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn leaf_name_of_path() {
+        assert_eq!("theme", leaf_name("config/ui/theme", '/'));
+        assert_eq!("theme", leaf_name("config.ui.theme", '.'));
+        assert_eq!("root", leaf_name("root", '/'));
+    }
+}
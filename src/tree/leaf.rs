@@ -0,0 +1,239 @@
+use flatbuffers::{FlatBufferBuilder, ForwardsUOffset, Table, VOffsetT, Vector};
+
+use crate::c::{self, *};
+
+// FlatBuffers vtable slots of the tree entities in the store model,
+// the id slots are always written, the native side fills them in.
+mod data_leaf {
+    use super::VOffsetT;
+    pub(super) const ID: VOffsetT = 4;
+    pub(super) const PARENT_BRANCH: VOffsetT = 6;
+    pub(super) const META_LEAF: VOffsetT = 8;
+    pub(super) const VALUE_INT: VOffsetT = 10;
+    pub(super) const VALUE_DOUBLE: VOffsetT = 12;
+    pub(super) const VALUE_STRING: VOffsetT = 14;
+    pub(super) const VALUE_STRINGS: VOffsetT = 16;
+}
+
+mod meta_leaf {
+    use super::VOffsetT;
+    pub(super) const ID: VOffsetT = 4;
+    pub(super) const NAME: VOffsetT = 6;
+    pub(super) const BRANCH: VOffsetT = 10;
+    pub(super) const VALUE_TYPE: VOffsetT = 12;
+}
+
+/// The value of a tree leaf
+#[derive(Debug, Clone, PartialEq)]
+pub enum LeafValue {
+    Int(i64),
+    Double(f64),
+    String(String),
+    Strings(Vec<String>),
+}
+
+impl LeafValue {
+    /// The type the leaf is stored with
+    pub fn property_type(&self) -> OBXPropertyType {
+        match self {
+            LeafValue::Int(_) => c::OBXPropertyType_Long,
+            LeafValue::Double(_) => c::OBXPropertyType_Double,
+            LeafValue::String(_) => c::OBXPropertyType_String,
+            LeafValue::Strings(_) => c::OBXPropertyType_StringVector,
+        }
+    }
+
+    /// Encodes the data leaf, for `TreeCursor::put_raw`
+    pub fn to_data_leaf(&self) -> Vec<u8> {
+        let mut builder = FlatBufferBuilder::new();
+        let string = match self {
+            LeafValue::String(s) => Some(builder.create_string(s)),
+            _ => None,
+        };
+        let strings = match self {
+            LeafValue::Strings(v) => {
+                let offsets: Vec<_> = v.iter().map(|s| builder.create_string(s)).collect();
+                Some(builder.create_vector(&offsets))
+            }
+            _ => None,
+        };
+
+        let table = builder.start_table();
+        builder.push_slot_always::<u64>(data_leaf::ID, 0);
+        builder.push_slot_always::<u64>(data_leaf::PARENT_BRANCH, 0);
+        builder.push_slot_always::<u64>(data_leaf::META_LEAF, 0);
+        match self {
+            LeafValue::Int(v) => builder.push_slot_always::<i64>(data_leaf::VALUE_INT, *v),
+            LeafValue::Double(v) => builder.push_slot_always::<f64>(data_leaf::VALUE_DOUBLE, *v),
+            _ => {}
+        }
+        if let Some(s) = string {
+            builder.push_slot_always(data_leaf::VALUE_STRING, s);
+        }
+        if let Some(v) = strings {
+            builder.push_slot_always(data_leaf::VALUE_STRINGS, v);
+        }
+        let end = builder.end_table(table);
+        builder.finish_minimal(end);
+        builder.finished_data().to_vec()
+    }
+
+    /// Decodes a data leaf, as returned by `TreeCursor::get_raw`.
+    /// With an unknown `value_type` (e.g. no meta leaf), the type is guessed
+    /// from the present values, a missing number is Int(0).
+    ///
+    /// # Safety
+    /// The FlatBuffers are read without verification, `data` must be
+    /// a data leaf from the native side or `to_data_leaf`.
+    pub unsafe fn from_data_leaf(data: &[u8], value_type: OBXPropertyType) -> Option<LeafValue> {
+        let table = root_table(data)?;
+        unsafe {
+            let int = || LeafValue::Int(table.get::<i64>(data_leaf::VALUE_INT, Some(0)).unwrap());
+            let double = || {
+                LeafValue::Double(
+                    table
+                        .get::<f64>(data_leaf::VALUE_DOUBLE, Some(0.0))
+                        .unwrap(),
+                )
+            };
+            let string = table
+                .get::<ForwardsUOffset<&str>>(data_leaf::VALUE_STRING, None)
+                .map(|s| LeafValue::String(s.to_string()));
+            let strings = table
+                .get::<ForwardsUOffset<Vector<ForwardsUOffset<&str>>>>(
+                    data_leaf::VALUE_STRINGS,
+                    None,
+                )
+                .map(|v| LeafValue::Strings(v.iter().map(|s| s.to_string()).collect()));
+
+            match value_type {
+                c::OBXPropertyType_Bool
+                | c::OBXPropertyType_Byte
+                | c::OBXPropertyType_Short
+                | c::OBXPropertyType_Char
+                | c::OBXPropertyType_Int
+                | c::OBXPropertyType_Long
+                | c::OBXPropertyType_Date
+                | c::OBXPropertyType_DateNano => Some(int()),
+                c::OBXPropertyType_Float | c::OBXPropertyType_Double => Some(double()),
+                c::OBXPropertyType_String => {
+                    Some(string.unwrap_or_else(|| LeafValue::String(String::new())))
+                }
+                c::OBXPropertyType_StringVector => {
+                    Some(strings.unwrap_or_else(|| LeafValue::Strings(Vec::new())))
+                }
+                _ => string.or(strings).or_else(|| {
+                    if table.get::<f64>(data_leaf::VALUE_DOUBLE, None).is_some() {
+                        Some(double())
+                    } else {
+                        Some(int())
+                    }
+                }),
+            }
+        }
+    }
+}
+
+impl From<i64> for LeafValue {
+    fn from(v: i64) -> Self {
+        LeafValue::Int(v)
+    }
+}
+
+impl From<f64> for LeafValue {
+    fn from(v: f64) -> Self {
+        LeafValue::Double(v)
+    }
+}
+
+impl From<&str> for LeafValue {
+    fn from(v: &str) -> Self {
+        LeafValue::String(v.to_string())
+    }
+}
+
+impl From<String> for LeafValue {
+    fn from(v: String) -> Self {
+        LeafValue::String(v)
+    }
+}
+
+impl From<Vec<String>> for LeafValue {
+    fn from(v: Vec<String>) -> Self {
+        LeafValue::Strings(v)
+    }
+}
+
+// only the root offset is checked, the rest of the table is trusted
+unsafe fn root_table(data: &[u8]) -> Option<Table<'_>> {
+    let loc = u32::from_le_bytes(data.get(0..4)?.try_into().ok()?) as usize;
+    if loc >= data.len() {
+        return None;
+    }
+    Some(Table::new(data, loc))
+}
+
+/// Encodes the meta leaf, which describes a leaf by name and type;
+/// missing branches and meta nodes are created along with it.
+pub fn to_meta_leaf(name: &str, value_type: OBXPropertyType) -> Vec<u8> {
+    let mut builder = FlatBufferBuilder::new();
+    let name = builder.create_string(name);
+    let table = builder.start_table();
+    builder.push_slot_always::<u64>(meta_leaf::ID, 0);
+    builder.push_slot_always(meta_leaf::NAME, name);
+    builder.push_slot_always::<u64>(meta_leaf::BRANCH, 0);
+    builder.push_slot_always::<u32>(meta_leaf::VALUE_TYPE, value_type);
+    let end = builder.end_table(table);
+    builder.finish_minimal(end);
+    builder.finished_data().to_vec()
+}
+
+/// The value type of a meta leaf, OBXPropertyType_Unknown if it has none
+///
+/// # Safety
+/// The FlatBuffers are read without verification, `metadata` must be
+/// a meta leaf from the native side or `to_meta_leaf`.
+pub unsafe fn meta_leaf_value_type(metadata: &[u8]) -> OBXPropertyType {
+    root_table(metadata)
+        .and_then(|table| table.get::<u32>(meta_leaf::VALUE_TYPE, None))
+        .unwrap_or(c::OBXPropertyType_Unknown)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn data_leaf_roundtrip() {
+        let values = [
+            LeafValue::Int(-42),
+            LeafValue::Double(2.5),
+            LeafValue::from("dark"),
+            LeafValue::from(vec!["a".to_string(), "b".to_string()]),
+        ];
+        for v in values {
+            let data = v.to_data_leaf();
+            assert_eq!(Some(v.clone()), unsafe {
+                LeafValue::from_data_leaf(&data, v.property_type())
+            });
+            // guessed from the present values
+            assert_eq!(Some(v.clone()), unsafe {
+                LeafValue::from_data_leaf(&data, c::OBXPropertyType_Unknown)
+            });
+        }
+        assert_eq!(None, unsafe {
+            LeafValue::from_data_leaf(&[1, 0], c::OBXPropertyType_Long)
+        });
+    }
+
+    #[test]
+    fn meta_leaf_value_type_roundtrip() {
+        let meta = to_meta_leaf("theme", c::OBXPropertyType_String);
+        assert_eq!(c::OBXPropertyType_String, unsafe {
+            meta_leaf_value_type(&meta)
+        });
+        assert_eq!(c::OBXPropertyType_Unknown, unsafe {
+            meta_leaf_value_type(&[])
+        });
+    }
+}
@@ -0,0 +1,250 @@
+#![allow(dead_code)]
+//! Hierarchical key-value data, addressed by paths like "config/ui/theme".
+//!
+//! A path consists of branches and ends in a leaf, which holds a typed value.
+//! Requires a native library with the tree feature, and a store model
+//! that contains the tree entities (e.g. the model shared with a Sync server).
+
+use std::ffi::{c_void, CString};
+use std::marker::PhantomData;
+use std::panic::{self, AssertUnwindSafe};
+use std::slice;
+
+use crate::c::{self, *};
+use crate::error::{self, Error, ErrorKind};
+use crate::r#box::PutMode;
+use crate::store::Store;
+use crate::util::NOT_FOUND_404;
+
+pub mod cursor;
+pub mod leaf;
+pub mod options;
+
+pub use cursor::{LeafInfo, RawLeaf, TreeCursor};
+pub use leaf::LeafValue;
+pub use options::{TreeOptionFlags, TreeOptions};
+
+type PutCallback = Box<dyn FnOnce(error::Result<obx_id>) + Send>;
+type GetCallback = Box<dyn FnOnce(error::Result<Option<LeafValue>>) + Send>;
+
+// The leaf buffers stay alive until the put went through
+struct PutState {
+    done: PutCallback,
+    data: Vec<u8>,
+    metadata: Vec<u8>,
+}
+
+unsafe extern "C" fn put_trampoline(status: obx_err, id: obx_id, user_data: *mut c_void) {
+    let state = *Box::from_raw(user_data as *mut PutState);
+    let done = state.done;
+    let r = panic::catch_unwind(AssertUnwindSafe(move || {
+        // OBX_NO_SUCCESS if the put mode prevented the put
+        done(match status {
+            0 => Ok(id),
+            _ => Err(c::status_error(status, "tree")),
+        })
+    }));
+    if r.is_err() {
+        eprintln!("Error: tree: callback panicked");
+    }
+}
+
+unsafe extern "C" fn get_trampoline(
+    status: obx_err,
+    _id: obx_id,
+    _path: *const std::ffi::c_char,
+    leaf_data: *const c_void,
+    leaf_data_size: usize,
+    leaf_metadata: *const c_void,
+    leaf_metadata_size: usize,
+    user_data: *mut c_void,
+) {
+    let done = *Box::from_raw(user_data as *mut GetCallback);
+    let r = panic::catch_unwind(AssertUnwindSafe(move || {
+        // the buffers are only valid during the callback
+        let result = match status {
+            0 if !leaf_data.is_null() => {
+                let data = slice::from_raw_parts(leaf_data as *const u8, leaf_data_size);
+                let value_type = if leaf_metadata.is_null() {
+                    c::OBXPropertyType_Unknown
+                } else {
+                    leaf::meta_leaf_value_type(slice::from_raw_parts(
+                        leaf_metadata as *const u8,
+                        leaf_metadata_size,
+                    ))
+                };
+                Ok(LeafValue::from_data_leaf(data, value_type))
+            }
+            0 | NOT_FOUND_404 => Ok(None),
+            _ => Err(c::status_error(status, "tree")),
+        };
+        done(result)
+    }));
+    if r.is_err() {
+        eprintln!("Error: tree: callback panicked");
+    }
+}
+
+/// A tree on top of a store, closed on drop.
+/// Must not outlive the Store.
+pub struct Tree<'s> {
+    pub(crate) obx_tree: *mut OBX_tree,
+    pub(crate) obx_store: *mut OBX_store,
+    pub(crate) options: TreeOptions,
+    phantom_data: PhantomData<&'s Store>,
+}
+
+impl Drop for Tree<'_> {
+    fn drop(&mut self) {
+        if !self.obx_tree.is_null() {
+            unsafe { obx_tree_close(self.obx_tree) };
+            self.obx_tree = std::ptr::null_mut();
+        }
+    }
+}
+
+fn to_c_path(path: &str) -> error::Result<CString> {
+    CString::new(path).map_err(|e| Error::new_local(&format!("Invalid tree path: {}", e)))
+}
+
+/// The last branch or leaf name of a path
+fn leaf_name(path: &str, path_delimiter: char) -> &str {
+    path.rsplit(path_delimiter).next().unwrap_or(path)
+}
+
+impl<'s> Tree<'s> {
+    /// With '/' as the path delimiter
+    pub fn new(store: &'s Store) -> error::Result<Self> {
+        Self::with_options(store, TreeOptions::default())
+    }
+
+    /// Fails with ErrorKind::FeatureNotAvailable if the native library
+    /// comes without trees
+    pub fn with_options(store: &'s Store, options: TreeOptions) -> error::Result<Self> {
+        if !Self::is_available() {
+            return Err(Error::new_local_kind(
+                ErrorKind::FeatureNotAvailable,
+                "Error: tree: not available in this native library",
+            ));
+        }
+        // always consumed by obx_tree
+        let obx_options = options.to_obx()?;
+        let obx_tree = c::new_mut(unsafe { obx_tree(store.obx_store, obx_options) })?;
+        Ok(Tree {
+            obx_tree,
+            obx_store: store.obx_store,
+            options,
+            phantom_data: PhantomData,
+        })
+    }
+
+    /// Whether the native library comes with trees
+    pub fn is_available() -> bool {
+        unsafe { obx_has_feature(OBXFeature_Tree) }
+    }
+
+    /// A cursor inside a new read transaction
+    pub fn read(&self) -> error::Result<TreeCursor<'_>> {
+        TreeCursor::new(self, false)
+    }
+
+    /// A cursor inside a new write transaction, see `TreeCursor::commit`
+    pub fn write(&self) -> error::Result<TreeCursor<'_>> {
+        TreeCursor::new(self, true)
+    }
+
+    /// The value at the path, in a read transaction of its own
+    pub fn get(&self, path: &str) -> error::Result<Option<LeafValue>> {
+        self.read()?.get(path)
+    }
+
+    /// Puts the value at the path in a write transaction of its own,
+    /// creating missing branches on the way. Returns the leaf id.
+    pub fn put(&self, path: &str, value: impl Into<LeafValue>) -> error::Result<obx_id> {
+        let cursor = self.write()?;
+        let id = cursor.put(path, value)?;
+        cursor.commit()?;
+        Ok(id)
+    }
+
+    /// Like `put`, on a background thread. `done` receives the leaf id, on that thread.
+    pub fn put_async(
+        &self,
+        path: &str,
+        value: impl Into<LeafValue>,
+        mode: PutMode,
+        done: impl FnOnce(error::Result<obx_id>) + Send + 'static,
+    ) -> error::Result<()> {
+        let c_path = to_c_path(path)?;
+        let value = value.into();
+        let name = leaf_name(path, self.options.path_delimiter);
+        let state = Box::into_raw(Box::new(PutState {
+            done: Box::new(done),
+            data: value.to_data_leaf(),
+            metadata: leaf::to_meta_leaf(name, value.property_type()),
+        }));
+        let r = c::call(unsafe {
+            obx_tree_async_put_raw(
+                self.obx_tree,
+                c_path.as_ptr(),
+                (*state).data.as_mut_ptr() as *mut c_void,
+                (*state).data.len(),
+                value.property_type(),
+                (*state).metadata.as_mut_ptr() as *mut c_void,
+                (*state).metadata.len(),
+                mode.to_obx(),
+                Some(put_trampoline),
+                state as *mut c_void,
+            )
+        });
+        if r.is_err() {
+            // not enqueued, the callback won't run
+            drop(unsafe { Box::from_raw(state) });
+        }
+        r
+    }
+
+    /// Like `get`, on a background thread. `done` receives the value, on that thread.
+    pub fn get_async(
+        &self,
+        path: &str,
+        done: impl FnOnce(error::Result<Option<LeafValue>>) + Send + 'static,
+    ) -> error::Result<()> {
+        let c_path = to_c_path(path)?;
+        let boxed: Box<GetCallback> = Box::new(Box::new(done));
+        let user_data = Box::into_raw(boxed) as *mut c_void;
+        let r = c::call(unsafe {
+            obx_tree_async_get_raw(
+                self.obx_tree,
+                c_path.as_ptr(),
+                true,
+                Some(get_trampoline),
+                user_data,
+            )
+        });
+        if r.is_err() {
+            drop(unsafe { Box::from_raw(user_data as *mut GetCallback) });
+        }
+        r
+    }
+
+    /// The number of tracked node conflicts (nodes of the same kind sharing a path),
+    /// see TreeOptionFlags::DETECT_NON_UNIQUE_NODES
+    pub fn node_conflict_count(&self) -> usize {
+        unsafe { obx_tree_node_conflict_count(self.obx_tree) }
+    }
+
+    /// Merges the conflicting nodes in a write transaction of its own,
+    /// returns how many were consolidated
+    pub fn consolidate_node_conflicts(&self) -> error::Result<usize> {
+        let cursor = self.write()?;
+        let count = cursor.consolidate_node_conflicts()?;
+        cursor.commit()?;
+        Ok(count)
+    }
+
+    /// Like `consolidate_node_conflicts`, on a background thread
+    pub fn consolidate_node_conflicts_async(&self) -> error::Result<()> {
+        c::call(unsafe { obx_tree_async_consolidate_node_conflicts(self.obx_tree) })
+    }
+}
@@ -0,0 +1,89 @@
+use crate::c::{self, *};
+use crate::error;
+use crate::util::flags;
+
+flags! {
+    /// Behavior flags for `TreeOptions::flags`, combine them with `|`
+    pub struct TreeOptionFlags;
+    const NONE = 0;
+    /// No debug logs for this tree, regardless of the store's debug flags
+    const DEBUG_LOGS_DISABLE = c::OBXTreeOptionFlags_DebugLogsDisable;
+    /// Debug logs for this tree, regardless of the store's debug flags
+    const DEBUG_LOGS_ENABLE = c::OBXTreeOptionFlags_DebugLogsEnable;
+    /// A path can't address a branch and a leaf at the same time
+    const ENFORCE_UNIQUE_PATH = c::OBXTreeOptionFlags_EnforceUniquePath;
+    /// Nodes of the same kind sharing a path (e.g. with Sync) are no error, the first one wins
+    const ALLOW_NON_UNIQUE_NODES = c::OBXTreeOptionFlags_AllowNonUniqueNodes;
+    /// Track non-unique nodes, see `Tree::node_conflict_count`
+    const DETECT_NON_UNIQUE_NODES = c::OBXTreeOptionFlags_DetectNonUniqueNodes;
+    /// Consolidate non-unique nodes on put/remove, implies DETECT_NON_UNIQUE_NODES
+    const AUTO_CONSOLIDATE_NON_UNIQUE_NODES = c::OBXTreeOptionFlags_AutoConsolidateNonUniqueNodes;
+}
+
+/// Structural options, passed to `Tree::with_options`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TreeOptions {
+    pub(crate) path_delimiter: char,
+    pub(crate) flags: TreeOptionFlags,
+}
+
+impl Default for TreeOptions {
+    fn default() -> Self {
+        TreeOptions {
+            path_delimiter: '/',
+            flags: TreeOptionFlags::NONE,
+        }
+    }
+}
+
+impl TreeOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Separates the branches of a path, '/' by default, must be ASCII
+    pub fn path_delimiter(mut self, path_delimiter: char) -> Self {
+        self.path_delimiter = path_delimiter;
+        self
+    }
+
+    pub fn flags(mut self, flags: TreeOptionFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// The native options, consumed by obx_tree
+    pub(crate) fn to_obx(self) -> error::Result<*mut OBX_tree_options> {
+        if !self.path_delimiter.is_ascii() {
+            return Err(error::Error::new_local(
+                "Error: tree: the path delimiter must be ASCII",
+            ));
+        }
+        let options = c::new_mut(unsafe { obx_tree_options() })?;
+        let r = c::call(unsafe {
+            obx_tree_opt_path_delimiter(options, self.path_delimiter as u8 as std::ffi::c_char)
+        })
+        .and_then(|_| c::call(unsafe { obx_tree_opt_flags(options, self.flags.bits()) }));
+        if let Err(err) = r {
+            unsafe { obx_tree_options_free(options) };
+            return Err(err);
+        }
+        Ok(options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn options_builder() {
+        let options = TreeOptions::new()
+            .path_delimiter('.')
+            .flags(TreeOptionFlags::ENFORCE_UNIQUE_PATH | TreeOptionFlags::DEBUG_LOGS_DISABLE);
+        assert_eq!('.', options.path_delimiter);
+        assert!(options.flags.contains(TreeOptionFlags::ENFORCE_UNIQUE_PATH));
+        assert_eq!(5, options.flags.bits());
+        assert_eq!('/', TreeOptions::default().path_delimiter);
+    }
+}
@@ -1,5 +1,12 @@
 #![allow(dead_code)]
+use std::marker::PhantomData;
+
+use flatbuffers::FlatBufferBuilder;
+
 use crate::c::*;
+use crate::cursor::Cursor;
+use crate::store::Store;
+use crate::traits::OBBlanket;
 use crate::{c, error};
 use crate::error::Error;
 
@@ -53,7 +60,8 @@ impl Tx {
         c::call(r)
     }
 
-    fn abort(&mut self) -> error::Result<()> {
+    // the txn still has to be closed after this, drop takes care of that
+    pub(crate) fn abort(&mut self) -> error::Result<()> {
         c::call(unsafe { obx_txn_abort(self.obx_txn) })
     }
 
@@ -65,3 +73,115 @@ impl Tx {
             .map(|_| (committed_size, size_change))
     }
 }
+
+/// A transaction handed out by `Store::read_tx` and `Store::write_tx`.
+/// Boxes fetched from it share this transaction,
+/// and cannot outlive it.
+pub struct Transaction<'s> {
+    store: &'s Store,
+    pub(crate) tx: Tx,
+    is_write: bool,
+}
+
+impl<'s> Transaction<'s> {
+    pub(crate) fn new(store: &'s Store, is_write: bool) -> error::Result<Self> {
+        let tx = if is_write {
+            Tx::new_mut(store.obx_store)
+        } else {
+            Tx::new(store.obx_store)
+        }?;
+        Ok(Transaction {
+            store,
+            tx,
+            is_write,
+        })
+    }
+
+    pub fn is_write(&self) -> bool {
+        self.is_write
+    }
+
+    pub fn get_box<T: 'static + OBBlanket>(&self) -> error::Result<TxBox<'_, T>> {
+        let helper = self.store.get_helper::<T>()?;
+        Cursor::from_txn(self.tx.obx_txn, helper).map(|cursor| TxBox {
+            cursor,
+            builder: FlatBufferBuilder::new(),
+            phantom: PhantomData,
+        })
+    }
+}
+
+/// A transaction-scoped Box, all operations run
+/// inside the Transaction it was fetched from.
+pub struct TxBox<'t, T: OBBlanket> {
+    cursor: Cursor<T>,
+    builder: FlatBufferBuilder<'static>,
+    phantom: PhantomData<&'t Transaction<'t>>,
+}
+
+impl<T: OBBlanket> TxBox<'_, T> {
+    pub fn put(&mut self, object: &mut T) -> error::Result<obx_id> {
+        self.cursor.put_entity(&mut self.builder, object)
+    }
+
+    pub fn put_many(&mut self, objects: Vec<&mut T>) -> error::Result<Vec<obx_id>> {
+        let mut vec_out = Vec::<obx_id>::new();
+        for o in objects {
+            vec_out.push(self.put(o)?);
+        }
+        Ok(vec_out)
+    }
+
+    pub fn get(&mut self, id: obx_id) -> error::Result<Option<T>> {
+        self.cursor.get_entity(id)
+    }
+
+    pub fn get_all(&mut self) -> error::Result<Vec<T>> {
+        self.cursor.get_all_entities()
+    }
+
+    pub fn remove_with_id(&mut self, id: obx_id) -> error::Result<()> {
+        self.cursor.remove(id)
+    }
+
+    pub fn remove_all(&mut self) -> error::Result<()> {
+        self.cursor.remove_all()
+    }
+
+    pub fn count(&mut self) -> error::Result<u64> {
+        self.cursor.count()
+    }
+
+    pub fn is_empty(&mut self) -> error::Result<bool> {
+        self.cursor.is_empty()
+    }
+
+    /// Add a ToMany relation between source and target entities, see `Box::rel_put`
+    pub fn rel_put(
+        &mut self,
+        relation_id: obx_schema_id,
+        source_id: obx_id,
+        target_id: obx_id,
+    ) -> error::Result<()> {
+        self.cursor.rel_put(relation_id, source_id, target_id)
+    }
+
+    /// Remove a ToMany relation between source and target entities, see `Box::rel_remove`
+    pub fn rel_remove(
+        &mut self,
+        relation_id: obx_schema_id,
+        source_id: obx_id,
+        target_id: obx_id,
+    ) -> error::Result<()> {
+        self.cursor.rel_remove(relation_id, source_id, target_id)
+    }
+
+    /// All target IDs of a ToMany relation, as seen by this transaction
+    pub fn rel_get_ids(
+        &self,
+        relation_id: obx_schema_id,
+        source_id: obx_id,
+    ) -> error::Result<Vec<obx_id>> {
+        self.cursor.rel_get_ids(relation_id, source_id)
+    }
+}
@@ -1,9 +1,10 @@
 #![allow(dead_code)]
-use crate::c;
+use crate::{c, error};
 use std::ffi::{c_void, CStr, CString};
 use std::os::raw::c_char;
 use std::path::Path;
 use std::ptr::null;
+use std::slice;
 
 // not using bindgen's derived #define OBX_NOT_FOUND 404, because it's a u32
 pub const NOT_FOUND_404: i32 = 404;
@@ -114,3 +115,181 @@ impl VecToPtrAndLength for Vec<&CStr> {
         (self.as_ptr() as *const T, self.len())
     }
 }
+
+/// Owns a native OBX_id_array, freed on drop
+pub(crate) struct IdArray {
+    ptr: *mut c::OBX_id_array,
+}
+
+impl Drop for IdArray {
+    fn drop(&mut self) {
+        if !self.ptr.is_null() {
+            unsafe { c::obx_id_array_free(self.ptr) };
+            self.ptr = std::ptr::null_mut();
+        }
+    }
+}
+
+impl IdArray {
+    /// The ids are copied into the native array
+    pub(crate) fn new(ids: &[c::obx_id]) -> error::Result<Self> {
+        Self::from_raw(unsafe { c::obx_id_array(ids.as_ptr(), ids.len()) })
+    }
+
+    /// Takes ownership of an array returned from a native call
+    pub(crate) fn from_raw(ptr: *mut c::OBX_id_array) -> error::Result<Self> {
+        c::new_mut(ptr).map(|ptr| IdArray { ptr })
+    }
+
+    pub(crate) fn as_ptr(&self) -> *const c::OBX_id_array {
+        self.ptr
+    }
+
+    pub(crate) fn to_vec(&self) -> Vec<c::obx_id> {
+        unsafe {
+            let array = &*self.ptr;
+            if array.count == 0 || array.ids.is_null() {
+                return Vec::new();
+            }
+            slice::from_raw_parts(array.ids, array.count).to_vec()
+        }
+    }
+}
+
+/// Owns a native OBX_bytes_array, freed on drop.
+/// obx_bytes_array_set does not copy, so the data set here is kept alive
+/// for as long as the array lives.
+pub(crate) struct BytesArray {
+    ptr: *mut c::OBX_bytes_array,
+    data: Vec<Vec<u8>>,
+}
+
+impl Drop for BytesArray {
+    fn drop(&mut self) {
+        if !self.ptr.is_null() {
+            unsafe { c::obx_bytes_array_free(self.ptr) };
+            self.ptr = std::ptr::null_mut();
+        }
+    }
+}
+
+impl BytesArray {
+    pub(crate) fn new(data: Vec<Vec<u8>>) -> error::Result<Self> {
+        let ptr = c::new_mut(unsafe { c::obx_bytes_array(data.len()) })?;
+        let array = BytesArray { ptr, data };
+        for (i, d) in array.data.iter().enumerate() {
+            c::call(unsafe {
+                c::obx_bytes_array_set(array.ptr, i, d.to_const_c_void(), d.len())
+            })?;
+        }
+        Ok(array)
+    }
+
+    /// Takes ownership of an array returned from a native call
+    pub(crate) fn from_raw(ptr: *mut c::OBX_bytes_array) -> error::Result<Self> {
+        c::new_mut(ptr).map(|ptr| BytesArray {
+            ptr,
+            data: Vec::new(),
+        })
+    }
+
+    pub(crate) fn as_ptr(&self) -> *const c::OBX_bytes_array {
+        self.ptr
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        unsafe { (*self.ptr).count }
+    }
+
+    /// None for missing objects, e.g. from obx_box_get_many
+    pub(crate) fn get(&self, index: usize) -> Option<&[u8]> {
+        unsafe {
+            let array = &*self.ptr;
+            if index >= array.count {
+                return None;
+            }
+            let bytes = &*array.bytes.add(index);
+            if bytes.data.is_null() {
+                None
+            } else {
+                Some(slice::from_raw_parts(bytes.data as *const u8, bytes.size))
+            }
+        }
+    }
+}
+
+/// Declares a newtype over the u32 of a native OBX*Flags type,
+/// with the given consts, `bits`, `contains`, `|`, `|=` and `From<_> for u32`
+macro_rules! flags {
+    (
+        $(#[$meta:meta])*
+        pub struct $name:ident;
+        $(
+            $(#[$flag_meta:meta])*
+            const $flag:ident = $value:expr;
+        )*
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+        pub struct $name(u32);
+
+        impl $name {
+            $(
+                $(#[$flag_meta])*
+                pub const $flag: Self = $name($value);
+            )*
+
+            pub fn bits(&self) -> u32 {
+                self.0
+            }
+
+            pub fn contains(&self, other: Self) -> bool {
+                self.0 & other.0 == other.0
+            }
+        }
+
+        impl std::ops::BitOr for $name {
+            type Output = Self;
+
+            fn bitor(self, rhs: Self) -> Self {
+                $name(self.0 | rhs.0)
+            }
+        }
+
+        impl std::ops::BitOrAssign for $name {
+            fn bitor_assign(&mut self, rhs: Self) {
+                self.0 |= rhs.0;
+            }
+        }
+
+        impl From<$name> for u32 {
+            fn from(flags: $name) -> Self {
+                flags.0
+            }
+        }
+    };
+}
+pub(crate) use flags;
+
+#[cfg(test)]
+mod tests {
+    flags! {
+        pub struct TestFlags;
+        const NONE = 0;
+        const A = 1;
+        const B = 8;
+    }
+
+    #[test]
+    fn combine_flags() {
+        let flags = TestFlags::A | TestFlags::B;
+        assert!(flags.contains(TestFlags::B));
+        assert!(!TestFlags::A.contains(flags));
+        assert_eq!(9, flags.bits());
+        assert_eq!(TestFlags::NONE, TestFlags::default());
+
+        let mut flags = TestFlags::NONE;
+        flags |= TestFlags::B;
+        assert_eq!(8, u32::from(flags));
+    }
+}